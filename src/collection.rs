@@ -1,4 +1,8 @@
-use crate::data::{Creator, Membership, Transaction, Trophy};
+use crate::data::{
+    CollectionInfo, CreatedGranularity, Creator, FeeMode, Membership, Transaction, Trophy,
+};
+use crate::events::{DonationEvent, DonationMatched, PauseStateChanged, Withdrawal};
+use crate::repository::repository::Repository;
 use crate::util::*;
 use scrypto::prelude::*;
 
@@ -10,35 +14,93 @@ pub struct CollectionArg {
     pub membership_resource_manager: ResourceManager,
     pub creator_resource_manager: ResourceManager,
     pub repository_owner_access_badge_address: ResourceAddress,
+    pub repository_address: ComponentAddress,
     pub creator_badge_proof: CheckedProof,
+    pub team_badges: Vec<NonFungibleGlobalId>,
     pub minter_badge: Bucket,
     pub creator_name: String,
     pub creator_slug: String,
     pub trophy_name: String,
     pub trophy_description: String,
+    pub accepted_resources: Vec<ResourceAddress>,
+    pub custom_info_url: Option<String>,
+    pub donation_cap: Option<Decimal>,
+    pub platform_fee_bps: u16,
     pub dapp_definition_address: GlobalAddress,
 }
 
 #[blueprint]
+#[events(DonationEvent, DonationMatched, PauseStateChanged, Withdrawal)]
 #[types(Trophy, Membership, Creator, Transaction)]
 mod collection {
     enable_method_auth! {
         roles {
             repository_owner => updatable_by: [];
-            owner => updatable_by: [];
+            repository => updatable_by: [];
+            collection => updatable_by: [];
+            owner => updatable_by: [collection];
         },
         methods {
             donate_mint => PUBLIC;
             donate_mint_with_membership => PUBLIC;
+            donate_mint_batch => PUBLIC;
+            refund_donation => PUBLIC;
+            donate_mint_no_fee => restrict_to: [repository_owner];
+            donate_mint_bonus => restrict_to: [repository_owner];
+            donate_mint_at_creation => restrict_to: [repository];
+            donate_mint_backfill => restrict_to: [repository_owner];
+            mint_replacement_admin_badge => restrict_to: [repository_owner];
             donate_update => PUBLIC;
+            donate_update_by_id => PUBLIC;
+            donate_update_to_total => PUBLIC;
             donate_update_with_membership => PUBLIC;
             withdraw_donations => restrict_to: [owner];
+            withdraw_amount => restrict_to: [owner];
+            withdraw_to_account => restrict_to: [owner];
+            withdraw_split => restrict_to: [owner];
             withdraw_fees => restrict_to: [repository_owner];
+            fund_matching => restrict_to: [owner];
+            withdraw_matching => restrict_to: [owner];
+            get_matching_remaining => PUBLIC;
+            set_fee_mode => restrict_to: [repository_owner];
+            get_fee_mode => PUBLIC;
+            lock_fee_mode => restrict_to: [repository_owner];
+            is_fee_mode_locked => PUBLIC;
+            quote_donation => PUBLIC;
             close_collection => restrict_to: [owner];
+            revoke_minter => restrict_to: [repository];
+            update_dapp_definition => restrict_to: [repository];
+            get_dapp_definition => PUBLIC;
+            set_min_donation => restrict_to: [owner];
+            set_refund_grace_period => restrict_to: [owner];
+            set_created_granularity => restrict_to: [owner];
+            get_created_granularity => PUBLIC;
+            set_donation_goal => restrict_to: [owner];
+            set_paused => restrict_to: [owner];
+            refresh_image_urls => restrict_to: [owner];
+            rotate_owner => restrict_to: [owner];
+            update_profile => restrict_to: [owner];
+            set_custom_metadata => restrict_to: [owner];
+            get_custom_metadata => PUBLIC;
+            get_progress => PUBLIC;
+            get_donation_cap => PUBLIC;
+            get_accepted_resources => PUBLIC;
+            is_collection_trophy => PUBLIC;
+            get_collection_info => PUBLIC;
+            get_user_metadata => PUBLIC;
+            get_donation_balance => PUBLIC;
+            get_supporter_count => PUBLIC;
+            get_created_at => PUBLIC;
+            get_version => PUBLIC;
+            get_total_withdrawn => PUBLIC;
+            preview_image_url => PUBLIC;
         }
     }
 
     struct Collection {
+        // The repository that made this collection, where minted trophies are indexed.
+        repository_address: ComponentAddress,
+
         // Mints a proof that is used as proof of donated value to the NFT repository.
         trophy_resource_manager: ResourceManager,
 
@@ -54,11 +116,26 @@ mod collection {
         // NFT minter badge
         minter_badge: Vault,
 
-        // Collected donations
-        donations: Vault,
+        // Resources accepted as donations
+        accepted_resources: Vec<ResourceAddress>,
+
+        // Collected donations per resource
+        donations: KeyValueStore<ResourceAddress, Vault>,
+
+        // Fees for the donations per resource
+        fees: KeyValueStore<ResourceAddress, Vault>,
+
+        // Budget of a sponsor per resource, used to match donations
+        matching: KeyValueStore<ResourceAddress, Vault>,
 
-        // Fees for the donations
-        fees: Vault,
+        // Share of every donation that is taken as fees
+        fee_mode: FeeMode,
+
+        // Whether the fee mode is permanently locked
+        fee_mode_locked: bool,
+
+        // Platform fee in basis points that is taken from every withdrawal
+        platform_fee_bps: u16,
 
         // Specific user name that owns this component
         creator_name: String,
@@ -72,14 +149,77 @@ mod collection {
         // Description of the trophy
         trophy_description: String,
 
+        // Custom info url of the trophies, used instead of the creator page when given
+        custom_info_url: Option<String>,
+
         // Which collection this collection component is for
         collection_id: String,
 
         // Creator badge address
         creator_badge_global_id: NonFungibleGlobalId,
 
+        // Creator badges the collection was handed over from, trophies and memberships minted
+        // before a rotation keep referring to them
+        former_creator_badges: Vec<NonFungibleGlobalId>,
+
         // Closed date for the collection
         closed: Option<UtcDateTime>,
+
+        // Minimum amount accepted for a single donation
+        min_donation: Decimal,
+
+        // Optional donation goal for the collection
+        donation_goal: Option<Decimal>,
+
+        // Optional cap on the total amount donated to the collection, set at construction
+        donation_cap: Option<Decimal>,
+
+        // Total amount donated to the collection
+        total_donated: Decimal,
+
+        // Whether donations are temporarily paused
+        paused: bool,
+
+        // Number of trophies minted by the collection
+        trophies_minted: u64,
+
+        // Number of minutes after a donation in which it can be refunded
+        refund_grace_period_minutes: i64,
+
+        // Created date for the collection
+        created_at: String,
+
+        // Version of the blueprint logic the collection was made with
+        version: u32,
+
+        // Total amount withdrawn by the creator
+        total_withdrawn: Decimal,
+
+        // Trophies minted with a promotional bonus, with the amount actually donated
+        bonus_trophies: KeyValueStore<NonFungibleLocalId, Decimal>,
+
+        // Trophies that received matched donations, with the total amount matched
+        matched_trophies: KeyValueStore<NonFungibleLocalId, Decimal>,
+
+        // Fees taken from the refundable donations to every trophy, returned when the trophy is
+        // refunded
+        trophy_fees: KeyValueStore<NonFungibleLocalId, Decimal>,
+
+        // Amount deposited to every trophy within its refund grace period, including the fees
+        // taken from it. Only this amount is returned when the trophy is refunded.
+        trophy_principal: KeyValueStore<NonFungibleLocalId, Decimal>,
+
+        // Time every trophy was minted at to the minute, the refund grace period starts from it
+        trophy_minted_at: KeyValueStore<NonFungibleLocalId, Instant>,
+
+        // Custom data set by the creator, e.g. social links or a bio
+        custom_metadata: KeyValueStore<String, String>,
+
+        // How precise the created date of new trophies is
+        created_granularity: CreatedGranularity,
+
+        // Team badges that can act as the collection admin next to the creator badge
+        team_badges: Vec<NonFungibleGlobalId>,
     }
 
     impl Collection {
@@ -95,21 +235,67 @@ mod collection {
                     .non_fungible_local_id(),
             );
 
+            // The creator badge and every team badge can act as the collection admin.
+            let mut admin_badges = vec![creator_badge_global_id.clone()];
+            admin_badges.extend(arg.team_badges.clone());
+
+            // Create a donation, fee and matching vault for every accepted resource.
+            let donations = KeyValueStore::new();
+            let fees = KeyValueStore::new();
+            let matching = KeyValueStore::new();
+            for resource in arg.accepted_resources.iter() {
+                donations.insert(*resource, Vault::new(*resource));
+                fees.insert(*resource, Vault::new(*resource));
+                matching.insert(*resource, Vault::new(*resource));
+            }
+
             Self {
+                repository_address: arg.repository_address,
                 minter_badge: Vault::with_bucket(arg.minter_badge),
-                donations: Vault::new(XRD),
-                fees: Vault::new(XRD),
+                accepted_resources: arg.accepted_resources,
+                donations,
+                fees,
+                matching,
+                // Collections start with the default fee, which the repository owner can change
+                // with set_fee_mode.
+                fee_mode: FeeMode::Percentage(400),
+                fee_mode_locked: false,
+                platform_fee_bps: arg.platform_fee_bps,
                 collection_id,
                 creator_badge_global_id: creator_badge_global_id.clone(),
+                former_creator_badges: vec![],
                 trophy_resource_manager: arg.trophy_resource_manager,
                 thanks_token_resource_manager: arg.thanks_token_resource_manager,
                 membership_resource_manager: arg.membership_resource_manager,
                 creator_resource_manager: arg.creator_resource_manager,
                 creator_name: arg.creator_name,
+                // The slug is validated where it is chosen, creator badges minted before slugs were
+                // restricted keep their slug and can still make collections.
                 creator_slug: arg.creator_slug,
                 trophy_name: arg.trophy_name,
                 trophy_description: arg.trophy_description,
+                custom_info_url: arg.custom_info_url,
                 closed: None,
+                min_donation: dec!(0),
+                donation_goal: None,
+                donation_cap: arg.donation_cap,
+                total_donated: dec!(0),
+                paused: false,
+                trophies_minted: 0,
+                refund_grace_period_minutes: DEFAULT_REFUND_GRACE_PERIOD_MINUTES,
+                created_at: generate_created_string(
+                    UtcDateTime::from_instant(&Clock::current_time_rounded_to_minutes()).unwrap(),
+                ),
+                total_withdrawn: dec!(0),
+                bonus_trophies: KeyValueStore::new(),
+                matched_trophies: KeyValueStore::new(),
+                trophy_fees: KeyValueStore::new(),
+                trophy_principal: KeyValueStore::new(),
+                trophy_minted_at: KeyValueStore::new(),
+                custom_metadata: KeyValueStore::new(),
+                created_granularity: CreatedGranularity::Minute,
+                team_badges: arg.team_badges,
+                version: COLLECTION_VERSION,
             }
             .instantiate()
             .prepare_to_globalize(OwnerRole::Fixed(rule!(require(
@@ -117,32 +303,262 @@ mod collection {
             ))))
             .metadata(metadata!(
                 roles {
-                    metadata_setter => rule!(require(arg.repository_owner_access_badge_address));
+                    metadata_setter => rule!(require(arg.repository_owner_access_badge_address) || require(global_caller(address)));
                     metadata_setter_updater => rule!(deny_all);
                     metadata_locker => rule!(deny_all);
                     metadata_locker_updater => rule!(deny_all);
                 },
                 init {
-                    "dapp_definition" => arg.dapp_definition_address, locked;
+                    "dapp_definition" => arg.dapp_definition_address, updatable;
                 }
             ))
             .roles(roles!(
                 repository_owner => rule!(require(arg.repository_owner_access_badge_address));
-                owner => rule!(require(creator_badge_global_id.clone()));
+                repository => rule!(require(global_caller(arg.repository_address)));
+                collection => rule!(require(global_caller(address)));
+                owner => rule!(require_any_of(admin_badges));
             ))
             .with_address(reservation)
             .globalize()
         }
 
-        // update_creator_metadata is a private method that updates the creator metadata based on
-        // the amount donated.
-        fn update_creator_metadata(&mut self, amount: Decimal) {
-            // Get the domain name used from the trophy resource manager.
-            let domain: String = self
-                .trophy_resource_manager
+        // assert_accepted_resource is a private method that rejects donations of any resource not
+        // accepted by this collection.
+        fn assert_accepted_resource(&self, resource: ResourceAddress) {
+            assert!(
+                self.accepted_resources.contains(&resource),
+                "The given resource is not accepted by this collection."
+            );
+        }
+
+        // assert_min_donation is a private method that rejects empty donations, and donations below
+        // the configured minimum donation amount.
+        fn assert_min_donation(&self, tokens: &Bucket) {
+            assert!(
+                tokens.amount() > Decimal::zero(),
+                "Donation must be more than zero."
+            );
+
+            assert!(
+                tokens.amount() >= self.min_donation,
+                "Donation below minimum of {} {}",
+                self.min_donation,
+                Runtime::bech32_encode_address(tokens.resource_address())
+            );
+        }
+
+        // push_minter_proof is a private method that pushes a proof of exactly one minter badge to
+        // the local auth zone, so the collection can call the repository with the minter role. The
+        // badge is gone once the repository revoked the minter, which is rejected with a clear
+        // message here instead of an opaque failure when creating the proof.
+        fn push_minter_proof(&self) {
+            assert!(
+                self.minter_badge.amount() >= dec!(1),
+                "Collection minter badge missing"
+            );
+
+            LocalAuthZone::push(self.minter_badge.as_fungible().create_proof_of_amount(1));
+        }
+
+        // domain is a private method that returns the domain name used for urls, as set in the
+        // metadata of the trophy resource manager.
+        fn domain(&self) -> String {
+            self.trophy_resource_manager
                 .get_metadata("domain")
                 .unwrap()
-                .expect("No domain on NFT repository");
+                .expect("No domain on NFT repository")
+        }
+
+        // url_template is a private method that gives the template for the image url of trophies,
+        // as set by the repository on the trophy resource.
+        fn url_template(&self) -> String {
+            self.trophy_resource_manager
+                .get_metadata("url_template")
+                .unwrap()
+                .expect("No url template on NFT repository")
+        }
+
+        // assert_donation_cap is a private method that rejects donations that would take the total
+        // donated over the donation cap. Donations that overshoot the cap are rejected as a whole.
+        fn assert_donation_cap(&self, amount: Decimal) {
+            if let Some(donation_cap) = self.donation_cap {
+                assert!(
+                    self.total_donated + amount <= donation_cap,
+                    "Donation would exceed the donation cap of {}.",
+                    donation_cap
+                );
+            }
+        }
+
+        // round_to_resource is a private method that rounds an amount towards zero to the
+        // divisibility of the given resource, so that computed amounts such as fees of resources
+        // with a divisibility below 18 can be taken from a bucket. The donated amount itself is
+        // never rounded, as a bucket always holds an amount within the divisibility of its resource.
+        fn round_to_resource(&self, resource: ResourceAddress, amount: Decimal) -> Decimal {
+            match ResourceManager::from(resource).resource_type() {
+                ResourceType::Fungible { divisibility } => {
+                    round_to_divisibility(amount, divisibility)
+                }
+                ResourceType::NonFungible { .. } => {
+                    panic!("The given resource is not a fungible resource.")
+                }
+            }
+        }
+
+        // deposit_donation is a private method that takes the fees from the donation to the given
+        // trophy by the given fee mode, and deposits the remaining tokens into the vault of the
+        // donated resource. Donations made within the refund grace period of the trophy are
+        // recorded per trophy together with their fees, so a refund returns exactly what was
+        // deposited and the fees that were taken, whatever the fee mode is at the time of the refund.
+        fn deposit_donation(
+            &mut self,
+            donor_trophy: NonFungibleLocalId,
+            mut tokens: Bucket,
+            fee_mode: FeeMode,
+        ) {
+            let resource = tokens.resource_address();
+            let principal = tokens.amount();
+            let fees = tokens
+                .take(self.round_to_resource(resource, compute_fee(&fee_mode, tokens.amount())));
+
+            let refundable = self
+                .refund_deadline(&donor_trophy)
+                .map(|deadline| {
+                    Clock::current_time_is_at_or_before(deadline, TimePrecision::Minute)
+                })
+                .unwrap_or(false);
+            if refundable {
+                let trophy_principal = self
+                    .trophy_principal
+                    .get(&donor_trophy)
+                    .map(|total| *total)
+                    .unwrap_or(dec!(0));
+                self.trophy_principal
+                    .insert(donor_trophy.clone(), trophy_principal + principal);
+
+                let trophy_fees = self
+                    .trophy_fees
+                    .get(&donor_trophy)
+                    .map(|total| *total)
+                    .unwrap_or(dec!(0));
+                self.trophy_fees
+                    .insert(donor_trophy, trophy_fees + fees.amount());
+            }
+
+            self.fees
+                .get_mut(&resource)
+                .expect("No fee vault for the given resource.")
+                .put(fees);
+            self.donations
+                .get_mut(&resource)
+                .expect("No donation vault for the given resource.")
+                .put(tokens);
+        }
+
+        // refund_deadline is a private method that returns the time until which the given trophy
+        // can be refunded, based on the time it was minted at. The created date can be of day
+        // granularity, so it is not used for the deadline. None is returned for trophies without a
+        // recorded mint time.
+        fn refund_deadline(&self, nft_id: &NonFungibleLocalId) -> Option<Instant> {
+            self.trophy_minted_at.get(nft_id).map(|minted_at| {
+                minted_at
+                    .add_minutes(self.refund_grace_period_minutes)
+                    .unwrap()
+            })
+        }
+
+        // take_matching is a private method that takes the matching amount for a donation of the
+        // given amount from the matching vault. The donation is matched one to one, until the
+        // budget of the sponsor runs out. An empty bucket is returned when there is no budget left.
+        fn take_matching(&mut self, resource: ResourceAddress, amount: Decimal) -> Bucket {
+            let mut matching = self.matching.get_mut(&resource).unwrap();
+            let matched = if amount < matching.amount() {
+                amount
+            } else {
+                matching.amount()
+            };
+
+            matching.take(matched)
+        }
+
+        // record_matching is a private method that records the amount matched for a trophy, and
+        // emits an event for it. The matched amount belongs to the sponsor, so the trophy is marked
+        // as not refundable. Nothing is recorded when the donation was not matched. It requires the
+        // minter badge proof on the local auth zone.
+        fn record_matching(&mut self, donor_trophy: NonFungibleLocalId, matched: Decimal) {
+            if matched == dec!(0) {
+                return;
+            }
+
+            self.trophy_resource_manager.update_non_fungible_data(
+                &donor_trophy,
+                "refundable",
+                false,
+            );
+
+            let total_matched = self
+                .matched_trophies
+                .get(&donor_trophy)
+                .map(|total| *total)
+                .unwrap_or(dec!(0));
+            self.matched_trophies
+                .insert(donor_trophy.clone(), total_matched + matched);
+
+            Runtime::emit_event(DonationMatched {
+                collection_id: self.collection_id.clone(),
+                donor_trophy,
+                matched,
+            });
+        }
+
+        // record_donation is a private method that adds the amount donated to the collection total,
+        // reports the new total to the repository, and emits a donation event for the trophy that
+        // was minted or updated.
+        fn record_donation(
+            &mut self,
+            donor_trophy: NonFungibleLocalId,
+            amount: Decimal,
+            minted: bool,
+            referrer: Option<String>,
+        ) {
+            self.record_unreported_donation(donor_trophy, amount, minted, referrer);
+            self.report_total();
+        }
+
+        // record_unreported_donation is a private method that does the same as record_donation,
+        // without reporting the new total to the repository.
+        fn record_unreported_donation(
+            &mut self,
+            donor_trophy: NonFungibleLocalId,
+            amount: Decimal,
+            minted: bool,
+            referrer: Option<String>,
+        ) {
+            self.total_donated = add_donated(self.total_donated, amount);
+
+            Runtime::emit_event(DonationEvent {
+                collection_id: self.collection_id.clone(),
+                donor_trophy,
+                amount,
+                new_total: self.total_donated,
+                minted,
+                trophies_minted: self.trophies_minted,
+                referrer,
+            });
+        }
+
+        // report_total is a private method that reports the total donated to the repository that
+        // keeps the totals of all collections. The minter badge proof on the local auth zone
+        // authorizes the call.
+        fn report_total(&self) {
+            let repository: Global<Repository> = self.repository_address.into();
+            repository.update_collection_total(self.collection_id.clone(), self.total_donated);
+        }
+
+        // update_creator_metadata is a private method that updates the creator metadata based on
+        // the amount donated. A negative amount takes a refunded donation off the funded total.
+        fn update_creator_metadata(&mut self, amount: Decimal) {
+            let domain = self.domain();
 
             let creator_nft_id = self.creator_badge_global_id.local_id();
 
@@ -150,7 +566,7 @@ mod collection {
                 .creator_resource_manager
                 .get_non_fungible_data(creator_nft_id);
 
-            data.funded += amount;
+            data.funded = add_donated(data.funded, amount);
             data.key_image_url = UncheckedUrl::of(generate_creator_url(
                 domain.to_string(),
                 data.funded,
@@ -171,12 +587,7 @@ mod collection {
 
         // mint_membership is a private method that mints a membership NFT based on the amount
         fn mint_membership(&mut self, donated: Decimal) -> Bucket {
-            // Get the domain name used from the trophy resource manager.
-            let domain: String = self
-                .trophy_resource_manager
-                .get_metadata("domain")
-                .unwrap()
-                .expect("No domain on NFT repository");
+            let domain = self.domain();
 
             let created = generate_created_string(
                 UtcDateTime::from_instant(&Clock::current_time_rounded_to_minutes()).unwrap(),
@@ -212,25 +623,16 @@ mod collection {
         // update_membership_metadata is a private method that updates the membership metadata based
         // on the amount donated.
         fn update_membership_metadata(&mut self, nft_id: NonFungibleLocalId, amount: Decimal) {
-            // Get the domain name used from the trophy resource manager.
-            let domain: String = self
-                .trophy_resource_manager
-                .get_metadata("domain")
-                .unwrap()
-                .expect("No domain on NFT repository");
+            let domain = self.domain();
 
             // Get data from the Membership data based on NF id.
             let mut data: Membership = self
                 .membership_resource_manager
                 .get_non_fungible_data(&nft_id);
 
-            assert_eq!(
-                data.creator_slug, self.creator_slug,
-                "The given membership does not match this component."
-            );
-
+            // The creator slug is not compared, as it can be changed with update_profile.
             assert!(
-                data.creator.eq(&self.creator_badge_global_id),
+                self.is_creator_badge(&data.creator),
                 "The given membership does not match this component."
             );
 
@@ -242,7 +644,8 @@ mod collection {
 
             // Generate new data based on the updated donation value.
             data.transactions.push(transaction);
-            data.donated += amount;
+            cap_transactions(&mut data.transactions);
+            data.donated = add_donated(data.donated, amount);
             data.key_image_url = UncheckedUrl::of(generate_membership_url(
                 domain.to_string(),
                 data.donated,
@@ -268,57 +671,130 @@ mod collection {
             );
         }
 
-        // mint_trophy is a private method that mints a trophy NFT based on the amount donated.
-        fn mint_trophy(&mut self, amount: Decimal) -> Bucket {
-            let domain: String = self
-                .trophy_resource_manager
-                .get_metadata("domain")
-                .unwrap()
-                .expect("No domain on NFT repository");
-
-            let created = generate_created_string(
-                UtcDateTime::from_instant(&Clock::current_time_rounded_to_minutes()).unwrap(),
+        // mint_trophy is a private method that mints a trophy NFT based on the amount donated, and
+        // registers it in the trophy index of the repository.
+        fn mint_trophy(
+            &mut self,
+            amount: Decimal,
+            currency: ResourceAddress,
+            original_donor: Option<ComponentAddress>,
+            message: Option<String>,
+            is_anonymous: bool,
+            referrer: Option<String>,
+        ) -> Bucket {
+            let trophy = self.mint_unindexed_trophy(
+                amount,
+                currency,
+                original_donor,
+                message,
+                is_anonymous,
+                referrer,
+                None,
             );
 
+            // Register the trophy in the index of the repository, the minter badge proof on the
+            // local auth zone authorizes the call.
+            let repository: Global<Repository> = self.repository_address.into();
+            repository.index_trophy(trophy.as_non_fungible().non_fungible_local_id());
+
+            trophy
+        }
+
+        // mint_unindexed_trophy is a private method that mints a trophy NFT based on the amount
+        // donated, without registering it in the trophy index of the repository. The trophy is
+        // created at the current time, unless a created date is given. The mint time is recorded
+        // to the minute whatever the created granularity, as the refund grace period starts from
+        // it.
+        fn mint_unindexed_trophy(
+            &mut self,
+            amount: Decimal,
+            currency: ResourceAddress,
+            original_donor: Option<ComponentAddress>,
+            message: Option<String>,
+            is_anonymous: bool,
+            referrer: Option<String>,
+            created: Option<String>,
+        ) -> Bucket {
+            let domain = self.domain();
+
+            let minted_at = created
+                .as_ref()
+                .map(|created| parse_created_string(created.clone()).to_instant())
+                .unwrap_or_else(Clock::current_time_rounded_to_minutes);
+
+            let created = created.unwrap_or_else(|| {
+                generate_created_string_with_granularity(
+                    UtcDateTime::from_instant(&minted_at).unwrap(),
+                    &self.created_granularity,
+                )
+            });
+
             let transaction = Transaction {
                 amount,
                 created: created.clone(),
             };
 
             // Create the trophy data.
+            // Anonymous trophies are displayed with a generic name instead of the trophy name.
+            let name = match is_anonymous {
+                true => ANONYMOUS_TROPHY_NAME.to_string(),
+                false => self.trophy_name.clone(),
+            };
+
             let data = Trophy {
-                name: self.trophy_name.clone(),
+                name,
                 description: self.trophy_description.clone(),
                 creator: self.creator_badge_global_id.clone(),
                 creator_name: self.creator_name.clone(),
                 creator_slug: self.creator_slug.clone(),
-                info_url: UncheckedUrl::of(format!("{}/p/{}", domain, self.creator_slug)),
+                info_url: UncheckedUrl::of(
+                    self.custom_info_url
+                        .clone()
+                        .unwrap_or_else(|| format!("{}/p/{}", domain, self.creator_slug)),
+                ),
                 collection_id: self.collection_id.clone(),
+                currency,
+                original_donor,
+                message,
+                is_anonymous,
+                referrer,
                 created: created.clone(),
                 transactions: vec![transaction],
                 donated: amount,
+                refundable: true,
+                tier: compute_tier(amount),
                 key_image_url: UncheckedUrl::of(generate_trophy_url(
+                    &self.url_template(),
                     domain.to_string(),
                     amount,
                     created.clone(),
                     self.collection_id.clone(),
+                    Runtime::bech32_encode_address(currency),
                 )),
             };
 
+            self.trophies_minted += 1;
+
             // Mint the trophy NFT.
-            self.trophy_resource_manager
-                .mint_ruid_non_fungible(data.clone())
+            let trophy = self
+                .trophy_resource_manager
+                .mint_ruid_non_fungible(data.clone());
+
+            self.trophy_minted_at
+                .insert(trophy.as_non_fungible().non_fungible_local_id(), minted_at);
+
+            trophy
         }
 
         // update_trophy_metadata is a private method that updates the trophy metadata based on the
         // amount donated.
-        fn update_trophy_metadata(&mut self, nft_id: NonFungibleLocalId, amount: Decimal) {
-            // Get the domain name used from the trophy resource manager.
-            let domain: String = self
-                .trophy_resource_manager
-                .get_metadata("domain")
-                .unwrap()
-                .expect("No domain on NFT repository");
+        fn update_trophy_metadata(
+            &mut self,
+            nft_id: NonFungibleLocalId,
+            amount: Decimal,
+            currency: ResourceAddress,
+        ) {
+            let domain = self.domain();
 
             // Get data from the Trophy data based on NF id.
             let mut data: Trophy = self.trophy_resource_manager.get_non_fungible_data(&nft_id);
@@ -326,28 +802,38 @@ mod collection {
             // Check whether the NF user_identity is owned by this component.
             assert_eq!(
                 data.collection_id, self.collection_id,
-                "The given trophy does match the collection id of this component."
+                "The given trophy does not match the collection id of this component."
             );
 
             assert!(
-                data.creator.eq(&self.creator_badge_global_id),
+                self.is_creator_badge(&data.creator),
                 "The given membership does not match this component."
             );
 
-            let created = generate_created_string(
+            assert_eq!(
+                data.currency, currency,
+                "The given trophy is not of the same currency as the donation."
+            );
+
+            let created = generate_created_string_with_granularity(
                 UtcDateTime::from_instant(&Clock::current_time_rounded_to_minutes()).unwrap(),
+                &self.created_granularity,
             );
 
             let transaction = Transaction { amount, created };
 
             // Generate new data based on the updated donation value.
             data.transactions.push(transaction);
-            data.donated += amount;
+            cap_transactions(&mut data.transactions);
+            data.donated = add_donated(data.donated, amount);
+            data.tier = compute_tier(data.donated);
             data.key_image_url = UncheckedUrl::of(generate_trophy_url(
+                &self.url_template(),
                 domain.to_string(),
                 data.donated,
                 data.created,
                 self.collection_id.clone(),
+                Runtime::bech32_encode_address(data.currency),
             ));
 
             // Update NF with new data
@@ -358,6 +844,8 @@ mod collection {
             );
             self.trophy_resource_manager
                 .update_non_fungible_data(&nft_id, "donated", data.donated);
+            self.trophy_resource_manager
+                .update_non_fungible_data(&nft_id, "tier", data.tier);
             self.trophy_resource_manager.update_non_fungible_data(
                 &nft_id,
                 "key_image_url",
@@ -366,184 +854,1309 @@ mod collection {
         }
 
         // donate_mint is a public method, callable by anyone who want to donate to the user. In
-        // return they will get a trophy NFT that represents the donation.
-        pub fn donate_mint(&mut self, mut tokens: Bucket) -> (Bucket, Bucket, Bucket) {
+        // return they will get a trophy NFT that represents the donation. The donor account is
+        // optionally recorded on the trophy as the original donor, together with an optional
+        // message from the donor. The id of the minted trophy is returned next to the buckets.
+        pub fn donate_mint(
+            &mut self,
+            mut tokens: Bucket,
+            donor: Option<ComponentAddress>,
+            message: Option<String>,
+            is_anonymous: bool,
+            referrer: Option<String>,
+        ) -> (Bucket, Bucket, Bucket, NonFungibleLocalId) {
             if self.closed.is_some() {
                 panic!("This collection is permanently closed.");
             }
 
+            assert!(!self.paused, "Collection is paused");
+
+            if let Some(message) = &message {
+                assert!(
+                    message.chars().count() <= MAX_MESSAGE_LENGTH,
+                    "Message must not be over {} characters.",
+                    MAX_MESSAGE_LENGTH
+                );
+            }
+
+            if let Some(referrer) = &referrer {
+                assert!(
+                    referrer.chars().count() <= MAX_REFERRER_LENGTH,
+                    "Referrer must not be over {} characters.",
+                    MAX_REFERRER_LENGTH
+                );
+            }
+
+            self.assert_accepted_resource(tokens.resource_address());
+            self.assert_min_donation(&tokens);
+
+            // The trophy is credited with the donation together with the matched amount.
+            let matched = self.take_matching(tokens.resource_address(), tokens.amount());
+            let donated = tokens.amount() + matched.amount();
+            self.assert_donation_cap(donated);
+
             // Push a proof of minter badge to the local auth zone for minting a trophy.
-            LocalAuthZone::push(self.minter_badge.as_fungible().create_proof_of_amount(1));
+            self.push_minter_proof();
 
             // Update creator badge
-            self.update_creator_metadata(tokens.amount());
+            self.update_creator_metadata(donated);
 
-            let trophy = self.mint_trophy(tokens.amount());
+            let trophy = self.mint_trophy(
+                donated,
+                tokens.resource_address(),
+                donor,
+                message,
+                is_anonymous,
+                referrer.clone(),
+            );
 
             let membership = self.mint_membership(tokens.amount());
 
             let thanks = self.thanks_token_resource_manager.mint(tokens.amount());
 
-            self.fees.put(tokens.take(tokens.amount() * dec!(0.04)));
+            let nft_id = trophy.as_non_fungible().non_fungible_local_id();
+            self.record_donation(nft_id.clone(), donated, true, referrer);
+            self.record_matching(nft_id.clone(), matched.amount());
 
-            self.donations.put(tokens);
+            tokens.put(matched);
+            self.deposit_donation(nft_id.clone(), tokens, self.fee_mode.clone());
 
-            (trophy, thanks, membership)
+            (trophy, thanks, membership, nft_id)
         }
 
-        // donate_mint_with_membership is a public method, callable by anyone who want to donate to
-        // the user. In return they will get a trophy NFT that represents the donation. This method
-        // requires a membership proof to be passed in.
-        pub fn donate_mint_with_membership(
+        // donate_mint_no_fee is a method for the repository owner to donate on behalf of the users
+        // of the platform, without taking any fees from the donation. The platform covers the fees
+        // for its own users, so the full donation goes to the creator.
+        pub fn donate_mint_no_fee(
             &mut self,
-            mut tokens: Bucket,
-            membership_proof: Proof,
-        ) -> (Bucket, Bucket) {
+            tokens: Bucket,
+            donor: Option<ComponentAddress>,
+        ) -> (Bucket, Bucket, Bucket) {
             if self.closed.is_some() {
                 panic!("This collection is permanently closed.");
             }
 
+            assert!(!self.paused, "Collection is paused");
+
+            self.assert_accepted_resource(tokens.resource_address());
+            self.assert_min_donation(&tokens);
+            self.assert_donation_cap(tokens.amount());
+
             // Push a proof of minter badge to the local auth zone for minting a trophy.
-            LocalAuthZone::push(self.minter_badge.as_fungible().create_proof_of_amount(1));
+            self.push_minter_proof();
 
             // Update creator badge
             self.update_creator_metadata(tokens.amount());
 
-            let checked_membership_proof =
-                membership_proof.check(self.membership_resource_manager.address());
-
-            // Update membership badge
-            self.update_membership_metadata(
-                checked_membership_proof
-                    .as_non_fungible()
-                    .non_fungible_local_id(),
+            let trophy = self.mint_trophy(
                 tokens.amount(),
+                tokens.resource_address(),
+                donor,
+                None,
+                false,
+                None,
             );
 
-            let trophy = self.mint_trophy(tokens.amount());
+            let membership = self.mint_membership(tokens.amount());
 
-            // Mint thanks tokens equal to the donated amount.
             let thanks = self.thanks_token_resource_manager.mint(tokens.amount());
 
-            // Take fees from the donation.
-            self.fees.put(tokens.take(tokens.amount() * dec!(0.04)));
+            self.record_donation(
+                trophy.as_non_fungible().non_fungible_local_id(),
+                tokens.amount(),
+                true,
+                None,
+            );
+
+            self.deposit_donation(
+                trophy.as_non_fungible().non_fungible_local_id(),
+                tokens,
+                FeeMode::Percentage(0),
+            );
 
-            // Take all tokens, and return trophy.
-            self.donations.put(tokens);
-            (trophy, thanks)
+            (trophy, thanks, membership)
         }
 
-        // donate_update is a public method, callable by anyone who want to donate to the user.
-        pub fn donate_update(
-            &mut self,
-            mut tokens: Bucket,
-            trophy_proof: Proof,
-        ) -> (Bucket, Bucket) {
-            if self.closed.is_some() {
-                panic!("This collection is permanently closed.");
-            }
+        // donate_mint_at_creation is a method for the repository to make the first donation to a
+        // collection it just made, in the same transaction. The repository can not be called back
+        // while it is making the collection, so the trophy is not indexed and the total is not
+        // reported here, the repository records both itself.
+        pub fn donate_mint_at_creation(&mut self, tokens: Bucket) -> (Bucket, Bucket, Bucket) {
+            self.assert_accepted_resource(tokens.resource_address());
+            self.assert_min_donation(&tokens);
+            self.assert_donation_cap(tokens.amount());
 
             // Push a proof of minter badge to the local auth zone for minting a trophy.
-            LocalAuthZone::push(self.minter_badge.as_fungible().create_proof_of_amount(1));
+            self.push_minter_proof();
 
             // Update creator badge
             self.update_creator_metadata(tokens.amount());
 
-            // Check that the proof is of same resource address.
-            let checked_proof = trophy_proof.check(self.trophy_resource_manager.address());
-
-            // Update trophy NF metadata
-            self.update_trophy_metadata(
-                checked_proof.as_non_fungible().non_fungible_local_id(),
+            let trophy = self.mint_unindexed_trophy(
                 tokens.amount(),
+                tokens.resource_address(),
+                None,
+                None,
+                false,
+                None,
+                None,
             );
 
             let membership = self.mint_membership(tokens.amount());
 
-            // Mint thanks tokens equal to the donated amount.
             let thanks = self.thanks_token_resource_manager.mint(tokens.amount());
 
-            // Take fees from the donation.
-            self.fees.put(tokens.take(tokens.amount() * dec!(0.04)));
+            self.record_unreported_donation(
+                trophy.as_non_fungible().non_fungible_local_id(),
+                tokens.amount(),
+                true,
+                None,
+            );
+
+            self.deposit_donation(
+                trophy.as_non_fungible().non_fungible_local_id(),
+                tokens,
+                self.fee_mode.clone(),
+            );
 
-            // Take all tokens, and return trophy.
-            self.donations.put(tokens);
-            (thanks, membership)
+            (trophy, thanks, membership)
         }
 
-        // donate_update_with_membership is a public method, callable by anyone who want to donate to the user.
-        // This method requires a membership proof, and trophy proof to be passed in.
-        pub fn donate_update_with_membership(
+        // donate_mint_backfill is a method for the repository owner to migrate a historical
+        // donation from another system, by minting a trophy with the given created date instead of
+        // the current time. It is restricted, as it could otherwise be used to fake the history of
+        // a collection. The created date must not be in the future.
+        pub fn donate_mint_backfill(
             &mut self,
-            mut tokens: Bucket,
-            trophy_proof: Proof,
-            membership_proof: Proof,
-        ) -> Bucket {
+            tokens: Bucket,
+            created: String,
+        ) -> (Bucket, Bucket, Bucket) {
             if self.closed.is_some() {
                 panic!("This collection is permanently closed.");
             }
 
+            assert!(!self.paused, "Collection is paused");
+
+            let created_at = parse_created_string(created.clone());
+            assert!(
+                Clock::current_time_is_at_or_after(created_at.to_instant(), TimePrecision::Minute),
+                "The created date {} is in the future.",
+                created
+            );
+
+            self.assert_accepted_resource(tokens.resource_address());
+            self.assert_min_donation(&tokens);
+            self.assert_donation_cap(tokens.amount());
+
             // Push a proof of minter badge to the local auth zone for minting a trophy.
-            LocalAuthZone::push(self.minter_badge.as_fungible().create_proof_of_amount(1));
+            self.push_minter_proof();
 
             // Update creator badge
             self.update_creator_metadata(tokens.amount());
 
-            let checked_membership_proof =
-                membership_proof.check(self.membership_resource_manager.address());
-
-            // Update membership badge
-            self.update_membership_metadata(
-                checked_membership_proof
-                    .as_non_fungible()
-                    .non_fungible_local_id(),
+            let trophy = self.mint_unindexed_trophy(
                 tokens.amount(),
+                tokens.resource_address(),
+                None,
+                None,
+                false,
+                None,
+                Some(created),
             );
 
-            // Check that the proof is of same resource address.
-            let checked_trophy_proof = trophy_proof.check(self.trophy_resource_manager.address());
+            // Register the trophy in the index of the repository, the minter badge proof on the
+            // local auth zone authorizes the call.
+            let repository: Global<Repository> = self.repository_address.into();
+            repository.index_trophy(trophy.as_non_fungible().non_fungible_local_id());
 
-            self.update_trophy_metadata(
-                checked_trophy_proof
-                    .as_non_fungible()
-                    .non_fungible_local_id(),
-                tokens.amount(),
-            );
+            let membership = self.mint_membership(tokens.amount());
 
-            // Mint thanks tokens equal to the donated amount.
             let thanks = self.thanks_token_resource_manager.mint(tokens.amount());
 
-            // Take fees from the donation.
-            self.fees.put(tokens.take(tokens.amount() * dec!(0.04)));
-
-            // Take all tokens, and return trophy.
-            self.donations.put(tokens);
-            thanks
-        }
+            self.record_donation(
+                trophy.as_non_fungible().non_fungible_local_id(),
+                tokens.amount(),
+                true,
+                None,
+            );
 
-        // withdraw_donations is a method for the admin to withdraw all donations.
-        pub fn withdraw_donations(&mut self) -> Bucket {
-            self.donations.take_all()
-        }
+            self.deposit_donation(
+                trophy.as_non_fungible().non_fungible_local_id(),
+                tokens,
+                self.fee_mode.clone(),
+            );
 
-        // withdraw_fees is a method for the repository owner to withdraw all fees.
-        pub fn withdraw_fees(&mut self) -> Bucket {
-            self.fees.take_all()
+            (trophy, thanks, membership)
         }
 
-        // close_collection is a method for the collection admin to close the collection
-        // permanently. This will prevent any further donations to be made to the collection, and
-        // will prevent any further minting or updating to the trophies.
-        pub fn close_collection(&mut self) -> Bucket {
+        // donate_mint_bonus is a method for the repository owner to donate during a promotion, where
+        // the donation counts for more on the trophy. The trophy records the donated amount times
+        // the multiplier, while only the actual tokens are deposited, so the donated amount shown
+        // on the trophy is higher than what the creator can withdraw. The thanks tokens, membership
+        // and collection totals follow the actual tokens. Bonus trophies can not be refunded.
+        pub fn donate_mint_bonus(
+            &mut self,
+            tokens: Bucket,
+            multiplier: Decimal,
+        ) -> (Bucket, Bucket, Bucket) {
             if self.closed.is_some() {
                 panic!("This collection is permanently closed.");
             }
 
-            self.closed =
-                Some(UtcDateTime::from_instant(&Clock::current_time_rounded_to_minutes()).unwrap());
+            assert!(!self.paused, "Collection is paused");
+
+            assert!(
+                multiplier >= dec!(1),
+                "Bonus multiplier must be 1 or more, got {}.",
+                multiplier
+            );
+
+            self.assert_accepted_resource(tokens.resource_address());
+            self.assert_min_donation(&tokens);
+            self.assert_donation_cap(tokens.amount());
+
+            // Push a proof of minter badge to the local auth zone for minting a trophy.
+            self.push_minter_proof();
+
+            // Update creator badge
+            self.update_creator_metadata(tokens.amount());
+
+            let trophy = self.mint_trophy(
+                tokens.amount() * multiplier,
+                tokens.resource_address(),
+                None,
+                None,
+                false,
+                None,
+            );
+
+            let nft_id = trophy.as_non_fungible().non_fungible_local_id();
+            self.bonus_trophies.insert(nft_id.clone(), tokens.amount());
+            self.trophy_resource_manager
+                .update_non_fungible_data(&nft_id, "refundable", false);
+
+            let membership = self.mint_membership(tokens.amount());
+
+            let thanks = self.thanks_token_resource_manager.mint(tokens.amount());
+
+            self.record_donation(nft_id.clone(), tokens.amount(), true, None);
+
+            self.deposit_donation(nft_id, tokens, self.fee_mode.clone());
+
+            (trophy, thanks, membership)
+        }
+
+        // donate_mint_batch is a public method for donating several donations at once, e.g. for a
+        // fundraising event. All donations are checked before anything is minted, so the batch
+        // either fully succeeds or fully aborts. A trophy is minted for every donation and returned
+        // in the same order as the donations, followed by the thanks tokens for the whole batch.
+        pub fn donate_mint_batch(&mut self, donations: Vec<Bucket>) -> Vec<Bucket> {
+            if self.closed.is_some() {
+                panic!("This collection is permanently closed.");
+            }
+
+            assert!(!self.paused, "Collection is paused");
+
+            assert!(
+                !donations.is_empty(),
+                "At least one donation must be given."
+            );
+
+            // Every donation is matched in order, until the budget of the sponsor runs out.
+            let mut total = dec!(0);
+            let mut thanks_total = dec!(0);
+            let mut matched_donations: Vec<(Bucket, Bucket)> = vec![];
+            for tokens in donations {
+                self.assert_accepted_resource(tokens.resource_address());
+                self.assert_min_donation(&tokens);
+
+                let matched = self.take_matching(tokens.resource_address(), tokens.amount());
+                total = add_donated(total, tokens.amount() + matched.amount());
+                thanks_total = add_donated(thanks_total, tokens.amount());
+                matched_donations.push((tokens, matched));
+            }
+            self.assert_donation_cap(total);
+
+            // Push a single proof of minter badge to the local auth zone for the whole batch.
+            self.push_minter_proof();
+
+            // Update creator badge
+            self.update_creator_metadata(total);
+
+            let mut buckets: Vec<Bucket> = vec![];
+            for (mut tokens, matched) in matched_donations {
+                let donated = tokens.amount() + matched.amount();
+                let trophy =
+                    self.mint_trophy(donated, tokens.resource_address(), None, None, false, None);
+
+                let nft_id = trophy.as_non_fungible().non_fungible_local_id();
+                self.record_donation(nft_id.clone(), donated, true, None);
+                self.record_matching(nft_id.clone(), matched.amount());
+
+                tokens.put(matched);
+                self.deposit_donation(nft_id, tokens, self.fee_mode.clone());
+                buckets.push(trophy);
+            }
+
+            buckets.push(self.thanks_token_resource_manager.mint(thanks_total));
+            buckets
+        }
+
+        // donate_mint_with_membership is a public method, callable by anyone who want to donate to
+        // the user. In return they will get a trophy NFT that represents the donation. This method
+        // requires a membership proof to be passed in. The donor account is optionally recorded on
+        // the trophy as the original donor.
+        pub fn donate_mint_with_membership(
+            &mut self,
+            mut tokens: Bucket,
+            membership_proof: Proof,
+            donor: Option<ComponentAddress>,
+        ) -> (Bucket, Bucket) {
+            if self.closed.is_some() {
+                panic!("This collection is permanently closed.");
+            }
+
+            assert!(!self.paused, "Collection is paused");
+
+            self.assert_accepted_resource(tokens.resource_address());
+            self.assert_min_donation(&tokens);
+
+            // The trophy is credited with the donation together with the matched amount.
+            let matched = self.take_matching(tokens.resource_address(), tokens.amount());
+            let donated = tokens.amount() + matched.amount();
+            self.assert_donation_cap(donated);
+
+            // Push a proof of minter badge to the local auth zone for minting a trophy.
+            self.push_minter_proof();
+
+            // Update creator badge
+            self.update_creator_metadata(donated);
+
+            let checked_membership_proof =
+                membership_proof.check(self.membership_resource_manager.address());
+
+            // Update membership badge
+            self.update_membership_metadata(
+                checked_membership_proof
+                    .as_non_fungible()
+                    .non_fungible_local_id(),
+                tokens.amount(),
+            );
+
+            let trophy =
+                self.mint_trophy(donated, tokens.resource_address(), donor, None, false, None);
+
+            // Mint thanks tokens equal to the donated amount.
+            let thanks = self.thanks_token_resource_manager.mint(tokens.amount());
+
+            let nft_id = trophy.as_non_fungible().non_fungible_local_id();
+            self.record_donation(nft_id.clone(), donated, true, None);
+            self.record_matching(nft_id.clone(), matched.amount());
+
+            // Take fees from the donation, and deposit the remaining tokens.
+            tokens.put(matched);
+            self.deposit_donation(nft_id, tokens, self.fee_mode.clone());
+            (trophy, thanks)
+        }
+
+        // donate_update is a public method, callable by anyone who want to donate to the user.
+        pub fn donate_update(
+            &mut self,
+            mut tokens: Bucket,
+            trophy_proof: Proof,
+        ) -> (Bucket, Bucket) {
+            if self.closed.is_some() {
+                panic!("This collection is permanently closed.");
+            }
+
+            assert!(!self.paused, "Collection is paused");
+
+            self.assert_accepted_resource(tokens.resource_address());
+            self.assert_min_donation(&tokens);
+
+            // The trophy is credited with the donation together with the matched amount.
+            let matched = self.take_matching(tokens.resource_address(), tokens.amount());
+            let donated = tokens.amount() + matched.amount();
+            self.assert_donation_cap(donated);
+
+            // Push a proof of minter badge to the local auth zone for minting a trophy.
+            self.push_minter_proof();
+
+            // Update creator badge
+            self.update_creator_metadata(donated);
+
+            // Check that the proof is of same resource address.
+            let checked_proof = trophy_proof.check(self.trophy_resource_manager.address());
+            let nft_id = checked_proof.as_non_fungible().non_fungible_local_id();
+
+            // Update trophy NF metadata
+            self.update_trophy_metadata(nft_id.clone(), donated, tokens.resource_address());
+
+            let membership = self.mint_membership(tokens.amount());
+
+            // Mint thanks tokens equal to the donated amount.
+            let thanks = self.thanks_token_resource_manager.mint(tokens.amount());
+
+            self.record_donation(nft_id.clone(), donated, false, None);
+            self.record_matching(nft_id.clone(), matched.amount());
+
+            // Take fees from the donation, and deposit the remaining tokens.
+            tokens.put(matched);
+            self.deposit_donation(nft_id, tokens, self.fee_mode.clone());
+            (thanks, membership)
+        }
+
+        // donate_update_by_id is a public method, callable by anyone who want to donate to the user
+        // on behalf of an existing trophy of this collection, e.g. to boost the trophy of another
+        // supporter. No proof of the trophy is required, the donor receives the thanks tokens and
+        // membership. The boosted amount belongs to the donor, so the trophy is marked as not
+        // refundable, the holder can otherwise refund the donation of someone else.
+        pub fn donate_update_by_id(
+            &mut self,
+            tokens: Bucket,
+            nft_id: NonFungibleLocalId,
+        ) -> (Bucket, Bucket) {
+            let matched = self.take_matching(tokens.resource_address(), tokens.amount());
+            let (thanks, membership) = self.donate_to_trophy(tokens, matched, nft_id.clone());
+            self.trophy_resource_manager
+                .update_non_fungible_data(&nft_id, "refundable", false);
+            (thanks, membership)
+        }
+
+        // donate_to_trophy is a private method that donates the given tokens to an existing trophy
+        // of this collection by its id, together with the given matched amount. It leaves the
+        // minter badge proof on the local auth zone.
+        fn donate_to_trophy(
+            &mut self,
+            mut tokens: Bucket,
+            matched: Bucket,
+            nft_id: NonFungibleLocalId,
+        ) -> (Bucket, Bucket) {
+            if self.closed.is_some() {
+                panic!("This collection is permanently closed.");
+            }
+
+            assert!(!self.paused, "Collection is paused");
+
+            self.assert_accepted_resource(tokens.resource_address());
+            self.assert_min_donation(&tokens);
+
+            let donated = tokens.amount() + matched.amount();
+            self.assert_donation_cap(donated);
+
+            assert!(
+                self.trophy_resource_manager.non_fungible_exists(&nft_id),
+                "No trophy exists with the given id."
+            );
+
+            // Push a proof of minter badge to the local auth zone for minting a trophy.
+            self.push_minter_proof();
+
+            // Update creator badge
+            self.update_creator_metadata(donated);
+
+            // Update trophy NF metadata
+            self.update_trophy_metadata(nft_id.clone(), donated, tokens.resource_address());
+
+            let membership = self.mint_membership(tokens.amount());
+
+            // Mint thanks tokens equal to the donated amount.
+            let thanks = self.thanks_token_resource_manager.mint(tokens.amount());
+
+            self.record_donation(nft_id.clone(), donated, false, None);
+            self.record_matching(nft_id.clone(), matched.amount());
+
+            // Take fees from the donation, and deposit the remaining tokens.
+            tokens.put(matched);
+            self.deposit_donation(nft_id, tokens, self.fee_mode.clone());
+            (thanks, membership)
+        }
+
+        // donate_update_to_total is a public method, callable by anyone who want to top up their
+        // trophy to an exact donated total. Only the amount needed to reach the target total is
+        // taken from the tokens, and the rest is returned as change together with the thanks
+        // tokens and membership. If the tokens are not enough to reach the target, all of them
+        // are donated.
+        pub fn donate_update_to_total(
+            &mut self,
+            mut tokens: Bucket,
+            trophy_proof: Proof,
+            target_total: Decimal,
+        ) -> (Bucket, Bucket, Bucket) {
+            let checked_proof = trophy_proof.check(self.trophy_resource_manager.address());
+            let nft_id = checked_proof.as_non_fungible().non_fungible_local_id();
+            let data: Trophy = self.trophy_resource_manager.get_non_fungible_data(&nft_id);
+
+            assert!(
+                target_total > data.donated,
+                "The trophy has already reached the target total of {}.",
+                target_total
+            );
+
+            let needed =
+                self.round_to_resource(tokens.resource_address(), target_total - data.donated);
+            let donation = if tokens.amount() > needed {
+                tokens.take(needed)
+            } else {
+                tokens.take(tokens.amount())
+            };
+
+            // The trophy proof is given, so the top up is a donation of the holder themselves. The
+            // donation is not matched, as the trophy would otherwise go past the target total.
+            let resource = donation.resource_address();
+            let (thanks, membership) =
+                self.donate_to_trophy(donation, Bucket::new(resource), nft_id);
+            (thanks, membership, tokens)
+        }
+
+        // donate_update_with_membership is a public method, callable by anyone who want to donate to the user.
+        // This method requires a membership proof, and trophy proof to be passed in.
+        pub fn donate_update_with_membership(
+            &mut self,
+            mut tokens: Bucket,
+            trophy_proof: Proof,
+            membership_proof: Proof,
+        ) -> Bucket {
+            if self.closed.is_some() {
+                panic!("This collection is permanently closed.");
+            }
+
+            assert!(!self.paused, "Collection is paused");
+
+            self.assert_accepted_resource(tokens.resource_address());
+            self.assert_min_donation(&tokens);
+
+            // The trophy is credited with the donation together with the matched amount.
+            let matched = self.take_matching(tokens.resource_address(), tokens.amount());
+            let donated = tokens.amount() + matched.amount();
+            self.assert_donation_cap(donated);
+
+            // Push a proof of minter badge to the local auth zone for minting a trophy.
+            self.push_minter_proof();
+
+            // Update creator badge
+            self.update_creator_metadata(donated);
+
+            let checked_membership_proof =
+                membership_proof.check(self.membership_resource_manager.address());
+
+            // Update membership badge
+            self.update_membership_metadata(
+                checked_membership_proof
+                    .as_non_fungible()
+                    .non_fungible_local_id(),
+                tokens.amount(),
+            );
+
+            // Check that the proof is of same resource address.
+            let checked_trophy_proof = trophy_proof.check(self.trophy_resource_manager.address());
+            let nft_id = checked_trophy_proof
+                .as_non_fungible()
+                .non_fungible_local_id();
+
+            self.update_trophy_metadata(nft_id.clone(), donated, tokens.resource_address());
+
+            // Mint thanks tokens equal to the donated amount.
+            let thanks = self.thanks_token_resource_manager.mint(tokens.amount());
+
+            self.record_donation(nft_id.clone(), donated, false, None);
+            self.record_matching(nft_id.clone(), matched.amount());
+
+            // Take fees from the donation, and deposit the remaining tokens.
+            tokens.put(matched);
+            self.deposit_donation(nft_id, tokens, self.fee_mode.clone());
+            thanks
+        }
+
+        // withdraw_donations is a method for the admin to withdraw all donations of the given
+        // resource. Withdrawing from an empty vault is rejected.
+        pub fn withdraw_donations(&mut self, resource: ResourceAddress) -> Bucket {
+            self.assert_accepted_resource(resource);
+
+            let donations = self.donations.get_mut(&resource).unwrap().take_all();
+            assert!(!donations.is_empty(), "No donations to withdraw.");
+
+            let donations = self.take_platform_fee(donations);
+            self.record_withdrawal(&donations);
+            donations
+        }
+
+        // refund_donation is a public method for donors to get a mistaken donation back, by
+        // returning the trophy within the refund grace period of its creation. The trophy must be
+        // returned together with the thanks tokens received for the refunded amount, both are
+        // burned and the amount donated within the grace period, including fees, is returned.
+        // Donations made to the trophy after the grace period stay with the creator. The funded
+        // total of the creator is reduced by the refunded amount. Memberships received for the
+        // donation are kept by the donor.
+        pub fn refund_donation(&mut self, trophy: Bucket, thanks: Bucket) -> Bucket {
+            assert_eq!(
+                trophy.resource_address(),
+                self.trophy_resource_manager.address(),
+                "The given trophy is not the of the same resource type as managed by the repository."
+            );
+
+            assert_eq!(
+                thanks.resource_address(),
+                self.thanks_token_resource_manager.address(),
+                "The given thanks tokens are not of the thanks token resource of the repository."
+            );
+
+            assert_eq!(
+                trophy.amount(),
+                dec!(1),
+                "Only a single trophy can be refunded at a time."
+            );
+
+            let data: Trophy = trophy.as_non_fungible().non_fungible::<Trophy>().data();
+
+            assert_eq!(
+                data.collection_id, self.collection_id,
+                "The given trophy does not match the collection id of this component."
+            );
+
+            // The donated amount of bonus trophies is more than what was deposited, matched amounts
+            // belong to the sponsor and boosts by id to the booster. Trophies made by a split or
+            // merge are not refundable either, so a bonus or matched trophy can not be split into
+            // refundable trophies.
+            assert!(data.refundable, "The given trophy can not be refunded.");
+
+            let nft_id = trophy.as_non_fungible().non_fungible_local_id();
+            let refund_deadline = self
+                .refund_deadline(&nft_id)
+                .expect("No mint time recorded for the given trophy.");
+            assert!(
+                Clock::current_time_is_at_or_before(refund_deadline, TimePrecision::Minute),
+                "The refund grace period of {} minutes has passed.",
+                self.refund_grace_period_minutes
+            );
+
+            // Only the amount donated within the grace period is refunded, together with the fees
+            // that were taken from it.
+            let principal = self
+                .trophy_principal
+                .remove(&nft_id)
+                .expect("No refundable donations recorded for the given trophy.");
+            let fee = self
+                .trophy_fees
+                .remove(&nft_id)
+                .expect("No fees recorded for the given trophy.");
+            self.trophy_minted_at.remove(&nft_id);
+
+            // Thanks tokens are minted one to one for the refundable donations of a trophy.
+            assert_eq!(
+                thanks.amount(),
+                principal,
+                "The {} thanks tokens received for the refunded donations must be returned, got {}.",
+                principal,
+                thanks.amount()
+            );
+
+            // The fees can be withdrawn by the repository owner at any time, in which case they
+            // can no longer be returned.
+            assert!(
+                self.fees.get(&data.currency).unwrap().amount() >= fee,
+                "The fees of the given trophy have already been withdrawn, it can no longer be refunded."
+            );
+            assert!(
+                self.donations.get(&data.currency).unwrap().amount() >= principal - fee,
+                "The donations of the given trophy have already been withdrawn, it can no longer be refunded."
+            );
+
+            // Take the donation back from the donation and fee vaults.
+            let mut refund = self
+                .donations
+                .get_mut(&data.currency)
+                .unwrap()
+                .take(principal - fee);
+            refund.put(self.fees.get_mut(&data.currency).unwrap().take(fee));
+
+            self.total_donated -= principal;
+
+            // Push a proof of minter badge to the local auth zone for updating the creator badge, and
+            // burning the trophy and the thanks tokens.
+            self.push_minter_proof();
+            self.update_creator_metadata(-principal);
+            trophy.burn();
+            thanks.burn();
+            self.report_total();
+
+            refund
+        }
+
+        // set_refund_grace_period is a method for the collection admin to set the number of
+        // minutes after a donation in which it can be refunded.
+        pub fn set_refund_grace_period(&mut self, minutes: i64) {
+            assert!(minutes >= 0, "Refund grace period must not be negative.");
+
+            self.refund_grace_period_minutes = minutes;
+        }
+
+        // set_created_granularity is a method for the collection admin to choose whether trophies
+        // get a created date with the minute, or only the day so that every trophy minted on the
+        // same day shares the same created value. It only applies to trophies minted and donations
+        // made after the change.
+        pub fn set_created_granularity(&mut self, granularity: CreatedGranularity) {
+            self.created_granularity = granularity;
+        }
+
+        // get_created_granularity returns how precise the created date of new trophies is.
+        pub fn get_created_granularity(&self) -> CreatedGranularity {
+            self.created_granularity.clone()
+        }
+
+        // update_profile is a method for the collection admin to change the creator name and slug,
+        // e.g. after a rebrand. Only trophies and memberships minted after the change carry the new
+        // values, existing trophies are updated with update_trophy_names and update_base_path of the
+        // repository. Trophies with the old and new values can not be merged with each other. The
+        // new slug must not be used by the collections of another creator.
+        pub fn update_profile(&mut self, creator_name: String, creator_slug: String) {
+            assert_ne!(
+                creator_name.len(),
+                0,
+                "Creator name must be 1 character or more."
+            );
+
+            assert!(
+                creator_name.len() <= 255,
+                "Creator name must not be over 255 characters"
+            );
+
+            let creator_slug = validate_slug(creator_slug);
+
+            // Slugs are unique per creator over the collections of the repository, the minter
+            // badge proof on the local auth zone authorizes the call.
+            self.push_minter_proof();
+            let repository: Global<Repository> = self.repository_address.into();
+            repository
+                .claim_creator_slug(creator_slug.clone(), self.creator_badge_global_id.clone());
+
+            self.creator_slug = creator_slug;
+            self.creator_name = creator_name;
+        }
+
+        // set_custom_metadata is a method for the collection admin to set custom data on the
+        // collection, e.g. social links or a bio. It is kept apart from the component metadata,
+        // which is locked.
+        pub fn set_custom_metadata(&mut self, key: String, value: String) {
+            assert_ne!(
+                key.len(),
+                0,
+                "Custom metadata key must be 1 character or more."
+            );
+
+            assert!(
+                key.len() <= MAX_CUSTOM_METADATA_KEY_LENGTH,
+                "Custom metadata key must not be over {} characters",
+                MAX_CUSTOM_METADATA_KEY_LENGTH
+            );
+
+            assert!(
+                value.len() <= MAX_CUSTOM_METADATA_VALUE_LENGTH,
+                "Custom metadata value must not be over {} characters",
+                MAX_CUSTOM_METADATA_VALUE_LENGTH
+            );
+
+            self.custom_metadata.insert(key, value);
+        }
+
+        // get_custom_metadata returns the custom data set on the collection for the given key.
+        pub fn get_custom_metadata(&self, key: String) -> Option<String> {
+            self.custom_metadata.get(&key).map(|value| value.clone())
+        }
+
+        // withdraw_to_account is a method for the admin to withdraw all donations of every accepted
+        // resource, and deposit them directly to the given account.
+        pub fn withdraw_to_account(&mut self, mut account: Global<Account>) {
+            for resource in self.accepted_resources.clone() {
+                // Resources without donations are skipped.
+                if self.donations.get(&resource).unwrap().is_empty() {
+                    continue;
+                }
+
+                let donations = self.withdraw_donations(resource);
+                account.try_deposit_or_abort(donations, None);
+            }
+        }
+
+        // withdraw_split is a method for the admin to withdraw all donations of every accepted
+        // resource, and divide them between several accounts, e.g. collaborators sharing revenue.
+        // Every recipient gets a share in basis points, and the shares must sum to 10000. Rounding
+        // dust goes to the last recipient.
+        pub fn withdraw_split(&mut self, mut recipients: Vec<(Global<Account>, u16)>) {
+            assert!(
+                !recipients.is_empty(),
+                "At least one recipient must be given."
+            );
+
+            let total_bps: u32 = recipients.iter().map(|(_, bps)| *bps as u32).sum();
+            assert_eq!(
+                total_bps, 10000,
+                "The shares of the recipients must sum to 10000 basis points."
+            );
+
+            let (last_account, others) = recipients.split_last_mut().unwrap();
+            for resource in self.accepted_resources.clone() {
+                // Resources without donations are skipped.
+                if self.donations.get(&resource).unwrap().is_empty() {
+                    continue;
+                }
+
+                let mut donations = self.withdraw_donations(resource);
+                let amount = donations.amount();
+                for (account, bps) in others.iter_mut() {
+                    let share =
+                        donations.take(self.round_to_resource(
+                            resource,
+                            amount * Decimal::from(*bps) / dec!(10000),
+                        ));
+                    account.try_deposit_or_abort(share, None);
+                }
+
+                last_account.0.try_deposit_or_abort(donations, None);
+            }
+        }
+
+        // withdraw_amount is a method for the admin to withdraw a specific amount of donations of
+        // the given resource.
+        pub fn withdraw_amount(&mut self, resource: ResourceAddress, amount: Decimal) -> Bucket {
+            self.assert_accepted_resource(resource);
+
+            let donations = {
+                let mut vault = self.donations.get_mut(&resource).unwrap();
+                assert!(
+                    vault.amount() >= amount,
+                    "Not enough donations to withdraw the given amount."
+                );
+
+                vault.take(amount)
+            };
+            let donations = self.take_platform_fee(donations);
+            self.record_withdrawal(&donations);
+            donations
+        }
+
+        // take_platform_fee is a private method that takes the platform fee from withdrawn
+        // donations, and deposits it to the fee vault. A platform fee of zero takes nothing.
+        fn take_platform_fee(&mut self, mut donations: Bucket) -> Bucket {
+            if self.platform_fee_bps > 0 {
+                let fee = donations.take(self.round_to_resource(
+                    donations.resource_address(),
+                    donations.amount() * Decimal::from(self.platform_fee_bps) / dec!(10000),
+                ));
+                self.fees
+                    .get_mut(&donations.resource_address())
+                    .expect("No fee vault for the given resource.")
+                    .put(fee);
+            }
+
+            donations
+        }
+
+        // record_withdrawal is a private method that adds the amount withdrawn by the creator to the
+        // collection total, and emits a withdrawal event.
+        fn record_withdrawal(&mut self, donations: &Bucket) {
+            self.total_withdrawn += donations.amount();
+
+            Runtime::emit_event(Withdrawal {
+                collection_id: self.collection_id.clone(),
+                amount: donations.amount(),
+                new_total: self.total_withdrawn,
+            });
+        }
+
+        // get_total_withdrawn returns the total amount withdrawn by the creator.
+        pub fn get_total_withdrawn(&self) -> Decimal {
+            self.total_withdrawn
+        }
+
+        // withdraw_fees is a method for the repository owner to withdraw all fees of the given
+        // resource.
+        pub fn withdraw_fees(&mut self, resource: ResourceAddress) -> Bucket {
+            self.assert_accepted_resource(resource);
+
+            self.fees.get_mut(&resource).unwrap().take_all()
+        }
+
+        // fund_matching is a method for the collection admin to add the budget of a sponsor, that is
+        // used to match donations to the collection one to one. Public donations are matched until
+        // the budget runs out, after which they are unmatched. Top ups with donate_update_to_total
+        // are not matched, as they must reach an exact total, and neither are the donations of the
+        // repository owner and the repository.
+        pub fn fund_matching(&mut self, tokens: Bucket) {
+            self.assert_accepted_resource(tokens.resource_address());
+
+            self.matching
+                .get_mut(&tokens.resource_address())
+                .unwrap()
+                .put(tokens);
+        }
+
+        // withdraw_matching is a method for the collection admin to take back the matching budget
+        // of the given resource that has not been used.
+        pub fn withdraw_matching(&mut self, resource: ResourceAddress) -> Bucket {
+            self.assert_accepted_resource(resource);
+
+            self.matching.get_mut(&resource).unwrap().take_all()
+        }
+
+        // get_matching_remaining returns the matching budget left for the given resource.
+        pub fn get_matching_remaining(&self, resource: ResourceAddress) -> Decimal {
+            self.assert_accepted_resource(resource);
+
+            self.matching.get(&resource).unwrap().amount()
+        }
+
+        // set_fee_mode is a method for the repository owner to change how fees are taken from every
+        // donation, either as a flat amount or as a percentage in basis points. Component royalties
+        // are flat per call, so the fees are taken from the donation itself. It only applies to
+        // donations made after the change.
+        pub fn set_fee_mode(&mut self, fee_mode: FeeMode) {
+            assert!(
+                !self.fee_mode_locked,
+                "The fee mode of this collection is locked."
+            );
+
+            validate_fee_mode(&fee_mode);
+
+            self.fee_mode = fee_mode;
+        }
+
+        // get_fee_mode returns how fees are taken from every donation.
+        pub fn get_fee_mode(&self) -> FeeMode {
+            self.fee_mode.clone()
+        }
+
+        // lock_fee_mode is a method for the repository owner to permanently lock the fee mode of
+        // the collection, as a guarantee to the creator that the cost of donating never changes.
+        // The fees are taken from the donations rather than as component royalties, so there are
+        // no royalties to lock. The lock can not be undone.
+        pub fn lock_fee_mode(&mut self) {
+            self.fee_mode_locked = true;
+        }
+
+        // is_fee_mode_locked returns whether the fee mode of the collection is permanently locked.
+        pub fn is_fee_mode_locked(&self) -> bool {
+            self.fee_mode_locked
+        }
+
+        // quote_donation returns how a donation of the given amount and resource would be split
+        // between the donation vault of the creator and the fees, using the same fee mode and
+        // rounding as when donating. The platform fee taken on withdrawal is not included.
+        pub fn quote_donation(
+            &self,
+            resource: ResourceAddress,
+            amount: Decimal,
+        ) -> (Decimal, Decimal) {
+            self.assert_accepted_resource(resource);
+
+            let fee = self.round_to_resource(resource, compute_fee(&self.fee_mode, amount));
+            (amount - fee, fee)
+        }
+
+        // set_min_donation is a method for the collection admin to set the minimum amount
+        // accepted for a single donation. A minimum of zero accepts any donation.
+        pub fn set_min_donation(&mut self, min_donation: Decimal) {
+            assert!(
+                min_donation >= dec!(0),
+                "Minimum donation must not be negative."
+            );
+
+            self.min_donation = min_donation;
+        }
+
+        // set_donation_goal is a method for the collection admin to set the donation goal of the
+        // collection. Setting the goal to None disables it, without resetting the total donated.
+        pub fn set_donation_goal(&mut self, donation_goal: Option<Decimal>) {
+            if let Some(goal) = donation_goal {
+                assert!(goal > dec!(0), "Donation goal must be over zero.");
+            }
+
+            self.donation_goal = donation_goal;
+        }
+
+        // get_progress returns the total donated to the collection together with the donation
+        // goal, if any.
+        pub fn get_progress(&self) -> (Decimal, Option<Decimal>) {
+            (self.total_donated, self.donation_goal)
+        }
+
+        // get_donation_cap returns the optional cap on the total amount donated to the collection.
+        pub fn get_donation_cap(&self) -> Option<Decimal> {
+            self.donation_cap
+        }
+
+        // is_collection_trophy returns whether the given proof holds trophies of this collection,
+        // e.g. to check access to gated content before calling donate_update. Unlike donate_update
+        // it returns false instead of panicking when the proof is of another resource or
+        // collection.
+        pub fn is_collection_trophy(&self, trophy_proof: Proof) -> bool {
+            if trophy_proof.resource_address() != self.trophy_resource_manager.address() {
+                return false;
+            }
+
+            // The resource address was checked above.
+            let checked_proof = trophy_proof.skip_checking();
+            let trophies = checked_proof.as_non_fungible().non_fungibles::<Trophy>();
+
+            !trophies.is_empty()
+                && trophies
+                    .iter()
+                    .all(|trophy| trophy.data().collection_id == self.collection_id)
+        }
+
+        // get_accepted_resources returns the resources accepted as donations by the collection, so
+        // clients know which resource to withdraw before building a donation manifest.
+        pub fn get_accepted_resources(&self) -> Vec<ResourceAddress> {
+            self.accepted_resources.clone()
+        }
+
+        // set_paused is a method for the collection admin to temporarily pause or resume donations
+        // to the collection. Withdrawals are still possible while the collection is paused.
+        pub fn set_paused(&mut self, paused: bool) {
+            self.paused = paused;
+
+            Runtime::emit_event(PauseStateChanged {
+                collection_id: self.collection_id.clone(),
+                paused,
+            });
+        }
+
+        // get_collection_info returns the information needed to render the collection, together
+        // with the current donation balance of every accepted resource.
+        pub fn get_collection_info(&self) -> CollectionInfo {
+            CollectionInfo {
+                creator_name: self.creator_name.clone(),
+                creator_slug: self.creator_slug.clone(),
+                creator: self.creator_badge_global_id.clone(),
+                collection_id: self.collection_id.clone(),
+                fee_mode: self.fee_mode.clone(),
+                donations: self.get_donation_balance(),
+            }
+        }
+
+        // get_user_metadata returns the identity, name and slug of the creator of the collection.
+        // The identity is the global id of the creator badge, as resource_address:local_id.
+        pub fn get_user_metadata(&self) -> (String, String, String) {
+            (
+                format!(
+                    "{}:{}",
+                    Runtime::bech32_encode_address(self.creator_badge_global_id.resource_address()),
+                    self.creator_badge_global_id.local_id()
+                ),
+                self.creator_name.clone(),
+                self.creator_slug.clone(),
+            )
+        }
+
+        // get_donation_balance returns the donations that can be withdrawn by the creator, per
+        // accepted resource.
+        pub fn get_donation_balance(&self) -> Vec<(ResourceAddress, Decimal)> {
+            self.accepted_resources
+                .iter()
+                .map(|resource| (*resource, self.donations.get(resource).unwrap().amount()))
+                .collect()
+        }
+
+        // get_supporter_count returns the number of trophies minted by the collection.
+        pub fn get_supporter_count(&self) -> u64 {
+            self.trophies_minted
+        }
+
+        // get_created_at returns the date the collection was created, in the same format as the
+        // created date of trophies.
+        pub fn get_created_at(&self) -> String {
+            self.created_at.clone()
+        }
+
+        // get_version returns the version of the blueprint logic the collection was made with.
+        pub fn get_version(&self) -> u32 {
+            self.version
+        }
+
+        // preview_image_url returns the image url a trophy of this collection would have for the
+        // given donated amount, created string and currency, using the same domain as when minting.
+        pub fn preview_image_url(
+            &self,
+            donated: Decimal,
+            created: String,
+            currency: ResourceAddress,
+        ) -> String {
+            let domain = self.domain();
+
+            generate_trophy_url(
+                &self.url_template(),
+                domain,
+                donated,
+                created,
+                self.collection_id.clone(),
+                Runtime::bech32_encode_address(currency),
+            )
+        }
+
+        // refresh_image_urls is a method for the collection admin to regenerate the image url and
+        // tier of the given trophies of this collection, e.g. after the domain or the tier
+        // thresholds have changed. The number of trophies per call is capped, so larger collections
+        // are refreshed over several transactions.
+        pub fn refresh_image_urls(&mut self, nft_ids: Vec<NonFungibleLocalId>) {
+            assert!(
+                nft_ids.len() <= MAX_BATCH_SIZE,
+                "Can not update more than {} trophies at a time.",
+                MAX_BATCH_SIZE
+            );
+
+            let domain = self.domain();
+
+            // Push a proof of minter badge to the local auth zone for updating the trophies.
+            self.push_minter_proof();
+
+            for nft_id in nft_ids.iter() {
+                let data: Trophy = self.trophy_resource_manager.get_non_fungible_data(nft_id);
+
+                assert_eq!(
+                    data.collection_id, self.collection_id,
+                    "The given trophy does not match the collection id of this component."
+                );
+
+                self.trophy_resource_manager.update_non_fungible_data(
+                    nft_id,
+                    "tier",
+                    compute_tier(data.donated),
+                );
+                self.trophy_resource_manager.update_non_fungible_data(
+                    nft_id,
+                    "key_image_url",
+                    UncheckedUrl::of(generate_trophy_url(
+                        &self.url_template(),
+                        domain.clone(),
+                        data.donated,
+                        data.created,
+                        data.collection_id,
+                        Runtime::bech32_encode_address(data.currency),
+                    )),
+                );
+            }
+        }
+
+        // rotate_owner is a method for the collection admin to hand the collection over to another
+        // creator badge. The owner role is the only role bound to the creator badge, so after the
+        // rotation the old creator badge loses all control over the collection, including
+        // withdrawals. The team badges keep acting as the collection admin. New trophies and
+        // memberships refer to the new creator badge and donations are funded to it, while existing
+        // trophies keep referring to the old one and can still be updated. Trophies with the old and
+        // new creator badge can not be merged with each other.
+        pub fn rotate_owner(&mut self, new_owner_badge: NonFungibleGlobalId) {
+            assert_eq!(
+                new_owner_badge.resource_address(),
+                self.creator_resource_manager.address(),
+                "The given badge is not a creator badge of the repository."
+            );
+            assert!(
+                self.creator_resource_manager
+                    .non_fungible_exists(new_owner_badge.local_id()),
+                "The given creator badge does not exist."
+            );
+
+            self.set_owner_badge(new_owner_badge);
+        }
+
+        // mint_replacement_admin_badge is a method for the repository owner to recover a collection
+        // when the creator has lost their creator badge. The repository owner badge is the root of
+        // recovery: a copy of the creator badge is minted and the owner role is rotated to it, so
+        // the lost badge loses all control over the collection. The team badges keep acting as the
+        // collection admin. This requires the minter badge, so it is not possible after the minter
+        // has been revoked.
+        pub fn mint_replacement_admin_badge(&mut self) -> Bucket {
+            // Creator badges can only be minted by the repository, the minter badge proof on the
+            // local auth zone authorizes the call.
+            self.push_minter_proof();
+
+            let repository: Global<Repository> = self.repository_address.into();
+            let badge = repository
+                .mint_replacement_creator_badge(self.creator_badge_global_id.local_id().clone());
+
+            self.set_owner_badge(NonFungibleGlobalId::new(
+                self.creator_resource_manager.address(),
+                badge.as_non_fungible().non_fungible_local_id(),
+            ));
+
+            badge
+        }
+
+        // set_owner_badge is a private method that hands the collection over to the given creator
+        // badge. New trophies and memberships refer to it, and the owner role is bound to it and the
+        // team badges of the collection.
+        fn set_owner_badge(&mut self, owner_badge: NonFungibleGlobalId) {
+            let former_owner_badge =
+                std::mem::replace(&mut self.creator_badge_global_id, owner_badge.clone());
+            self.former_creator_badges.push(former_owner_badge);
+
+            let mut admin_badges = vec![owner_badge];
+            admin_badges.extend(self.team_badges.clone());
+
+            Runtime::global_component().set_role("owner", rule!(require_any_of(admin_badges)));
+        }
+
+        // is_creator_badge is a private method that checks whether the given badge is the current
+        // or a former creator badge of the collection.
+        fn is_creator_badge(&self, badge: &NonFungibleGlobalId) -> bool {
+            badge.eq(&self.creator_badge_global_id) || self.former_creator_badges.contains(badge)
+        }
+
+        // revoke_minter is a method for the repository that made this collection to permanently
+        // revoke the ability of the collection to mint and update trophies, e.g. when the
+        // collection is compromised. The minter badge can not be withdrawn, so it is burned here.
+        // The collection is closed as well, while withdrawals of donations are still possible.
+        pub fn revoke_minter(&mut self) {
+            self.minter_badge.burn(self.minter_badge.amount());
+
+            if self.closed.is_none() {
+                self.closed = Some(
+                    UtcDateTime::from_instant(&Clock::current_time_rounded_to_minutes()).unwrap(),
+                );
+            }
+        }
+
+        // update_dapp_definition is a method for the repository that made this collection to point
+        // the collection to a new dapp definition.
+        pub fn update_dapp_definition(&mut self, new_address: GlobalAddress) {
+            Runtime::global_component().set_metadata("dapp_definition", new_address);
+        }
+
+        // get_dapp_definition returns the dapp definition the collection is associated with, as
+        // set in the metadata of the collection.
+        pub fn get_dapp_definition(&self) -> GlobalAddress {
+            Runtime::global_component()
+                .get_metadata("dapp_definition")
+                .unwrap()
+                .expect("No dapp definition on collection")
+        }
+
+        // close_collection is a method for the collection admin to close the collection
+        // permanently. This will prevent any further donations to be made to the collection, and
+        // burns the minter badge so no further trophies can be minted or updated by the collection.
+        // Withdrawals are still possible after the collection is closed.
+        pub fn close_collection(&mut self) -> Vec<Bucket> {
+            if self.closed.is_some() {
+                panic!("This collection is permanently closed.");
+            }
+
+            self.closed =
+                Some(UtcDateTime::from_instant(&Clock::current_time_rounded_to_minutes()).unwrap());
+
+            // Revoke the ability to mint by burning the minter badge.
+            self.minter_badge.burn(self.minter_badge.amount());
 
             // Withdraw all remaining donations.
-            self.donations.take_all()
+            self.accepted_resources
+                .clone()
+                .iter()
+                .map(|resource| {
+                    let donations = self.donations.get_mut(resource).unwrap().take_all();
+                    let donations = self.take_platform_fee(donations);
+                    self.record_withdrawal(&donations);
+                    donations
+                })
+                .collect()
         }
     }
 }
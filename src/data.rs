@@ -8,21 +8,40 @@ pub struct Transaction {
 
 #[derive(ScryptoSbor, NonFungibleData, Clone)]
 pub struct Trophy {
+    #[mutable]
     pub name: String,
     pub description: String,
     pub creator: NonFungibleGlobalId,
     pub creator_name: String,
     pub creator_slug: String,
+    #[mutable]
     pub info_url: UncheckedUrl,
+    #[mutable]
     pub collection_id: String,
+    pub currency: ResourceAddress,
+    pub original_donor: Option<ComponentAddress>,
+    pub message: Option<String>,
+    pub is_anonymous: bool,
+    pub referrer: Option<String>,
     pub created: String,
 
     #[mutable]
     pub transactions: Vec<Transaction>,
 
+    // The full amount given by the donor. The fees are taken from this amount, so it equals what
+    // the creator can withdraw plus the fees withdrawn by the repository owner.
     #[mutable]
     pub donated: Decimal,
 
+    // Whether the trophy can be refunded. Trophies credited with more than the donor deposited,
+    // e.g. with a matched donation or a bonus, are not, and neither are trophies made by a split or
+    // merge.
+    #[mutable]
+    pub refundable: bool,
+
+    #[mutable]
+    pub tier: String,
+
     #[mutable]
     pub key_image_url: UncheckedUrl,
 }
@@ -61,3 +80,29 @@ pub struct Creator {
     #[mutable]
     pub key_image_url: UncheckedUrl,
 }
+
+// How fees are taken from donations. A flat fee takes the same amount of the donated resource from
+// every donation, while a percentage fee takes a share given in basis points.
+#[derive(ScryptoSbor, ManifestSbor, Clone, Debug, PartialEq, Eq)]
+pub enum FeeMode {
+    Flat(Decimal),
+    Percentage(u16),
+}
+
+// How precise the created date of new trophies is. With day granularity every trophy minted on the
+// same day shares the same created value.
+#[derive(ScryptoSbor, ManifestSbor, Clone, Debug, PartialEq, Eq)]
+pub enum CreatedGranularity {
+    Minute,
+    Day,
+}
+
+#[derive(ScryptoSbor, Clone)]
+pub struct CollectionInfo {
+    pub creator_name: String,
+    pub creator_slug: String,
+    pub creator: NonFungibleGlobalId,
+    pub collection_id: String,
+    pub fee_mode: FeeMode,
+    pub donations: Vec<(ResourceAddress, Decimal)>,
+}
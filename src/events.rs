@@ -0,0 +1,72 @@
+use scrypto::prelude::*;
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct DonationEvent {
+    pub collection_id: String,
+    pub donor_trophy: NonFungibleLocalId,
+    pub amount: Decimal,
+    pub new_total: Decimal,
+    pub minted: bool,
+    pub trophies_minted: u64,
+    pub referrer: Option<String>,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct CollectionDeregistered {
+    pub collection_id: String,
+    pub component: ComponentAddress,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct DonationMatched {
+    pub collection_id: String,
+    pub donor_trophy: NonFungibleLocalId,
+    pub matched: Decimal,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct PauseStateChanged {
+    pub collection_id: String,
+    pub paused: bool,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct TrophyBurned {
+    pub collection_id: String,
+    pub donated: Decimal,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct TrophyMerged {
+    pub collection_id: String,
+    pub merged_ids: Vec<NonFungibleLocalId>,
+    pub merged_count: u64,
+    pub total_donated: Decimal,
+    pub earliest_created: String,
+    pub result_id: NonFungibleLocalId,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct CollectionCreated {
+    pub collection_id: String,
+    pub component: ComponentAddress,
+    pub creator_slug: String,
+    pub creator_badge_address: ResourceAddress,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct ImageUrlsMigrated {
+    pub migrated_count: u64,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct RepositoryFrozen {
+    pub frozen: bool,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct Withdrawal {
+    pub collection_id: String,
+    pub amount: Decimal,
+    pub new_total: Decimal,
+}
@@ -1,4 +1,5 @@
 mod collection;
 pub mod data;
+pub mod events;
 mod repository;
-mod util;
+pub mod util;
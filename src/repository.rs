@@ -1,36 +1,108 @@
 use crate::collection::collection::Collection;
-use crate::data::{Creator, Membership, Transaction, Trophy};
+use crate::data::{CreatedGranularity, Creator, Membership, Transaction, Trophy};
+use crate::events::{
+    CollectionCreated, CollectionDeregistered, ImageUrlsMigrated, RepositoryFrozen, TrophyBurned,
+    TrophyMerged,
+};
 use crate::util::*;
 use scrypto::prelude::*;
 
 #[blueprint]
+#[events(
+    CollectionCreated,
+    CollectionDeregistered,
+    ImageUrlsMigrated,
+    RepositoryFrozen,
+    TrophyBurned,
+    TrophyMerged
+)]
 #[types(Trophy, Membership, Creator, Transaction)]
 mod repository {
     use crate::collection::CollectionArg;
-    use std::cmp::Ordering;
     enable_package_royalties! {
         new => Free;
         merge_trophies => Free;
+        merge_trophies_grouped => Free;
+        preview_merge => Free;
+        sum_donations => Free;
+        earliest_created => Free;
+        split_trophy => Free;
+        burn_trophy => Free;
+        revoke_collection_minter => Free;
+        deregister_collection => Free;
         merge_memberships => Free;
         new_collection_component => Usd(5.into());
         new_collection_component_and_badge => Usd(5.into());
+        new_collection_and_donate => Usd(5.into());
         mint_creator_badge => Free;
+        mint_replacement_creator_badge => Free;
+        claim_creator_slug => Free;
         redeem_thanks_token => Free;
         close_repository => Free;
+        set_frozen => Free;
+        update_trophy_names => Free;
+        update_base_path => Free;
+        migrate_image_urls => Free;
+        remap_collection_id => Free;
+        update_dapp_definition => Free;
+        update_collections_dapp_definition => Free;
+        get_dapp_definition => Free;
+        get_collection => Free;
+        get_trophy => Free;
+        filter_trophies => Free;
+        export_trophy => Free;
+        get_trophy_resource_address => Free;
+        get_minter_badges_issued => Free;
+        index_trophy => Free;
+        get_collection_trophies => Free;
+        get_collection_trophy_count => Free;
+        update_collection_total => Free;
+        get_collection_total => Free;
     }
 
     enable_method_auth! {
         roles {
             admin => updatable_by: [OWNER];
+            minter => updatable_by: [];
         },
         methods {
             new_collection_component => PUBLIC;
             new_collection_component_and_badge => PUBLIC;
+            new_collection_and_donate => PUBLIC;
             mint_creator_badge => PUBLIC;
+            mint_replacement_creator_badge => restrict_to: [minter];
+            claim_creator_slug => restrict_to: [minter];
             merge_trophies => PUBLIC;
+            merge_trophies_grouped => PUBLIC;
+            preview_merge => PUBLIC;
+            sum_donations => PUBLIC;
+            earliest_created => PUBLIC;
+            split_trophy => PUBLIC;
+            burn_trophy => PUBLIC;
             merge_memberships => PUBLIC;
             redeem_thanks_token => PUBLIC;
             close_repository => restrict_to: [admin];
+            set_frozen => restrict_to: [admin];
+            update_trophy_names => restrict_to: [admin];
+            update_base_path => restrict_to: [admin];
+            migrate_image_urls => restrict_to: [admin];
+            remap_collection_id => restrict_to: [admin];
+            update_dapp_definition => restrict_to: [admin];
+            update_collections_dapp_definition => restrict_to: [admin];
+            get_dapp_definition => PUBLIC;
+            revoke_collection_minter => restrict_to: [admin];
+            deregister_collection => restrict_to: [admin];
+            get_collection => PUBLIC;
+            get_trophy => PUBLIC;
+            filter_trophies => PUBLIC;
+            export_trophy => PUBLIC;
+            get_trophy_resource_address => PUBLIC;
+            get_minter_badges_issued => PUBLIC;
+            index_trophy => restrict_to: [minter];
+            get_collection_trophies => PUBLIC;
+            get_collection_trophy_count => PUBLIC;
+            update_collection_total => restrict_to: [minter];
+            get_collection_total => PUBLIC;
         }
     }
 
@@ -56,8 +128,38 @@ mod repository {
         // Dapp definition address
         dapp_definition_address: GlobalAddress,
 
+        // Platform fee in basis points taken from withdrawals of every collection
+        platform_fee_bps: u16,
+
+        // Collections made by the repository, keyed by collection id
+        collections: KeyValueStore<String, ComponentAddress>,
+
+        // Creator badge that first made a collection with every creator slug, keyed by slug
+        collection_slugs: KeyValueStore<String, NonFungibleGlobalId>,
+
+        // Ids of the trophies minted for every collection, keyed by collection id and position
+        collection_trophies: KeyValueStore<(String, u64), NonFungibleLocalId>,
+
+        // Number of trophies in the index of every collection, keyed by collection id
+        collection_trophy_counts: KeyValueStore<String, u64>,
+
+        // Total donated to every collection, keyed by collection id
+        collection_totals: KeyValueStore<String, Decimal>,
+
+        // Template for the image url of trophies
+        url_template: String,
+
+        // Number of minter badges handed out to collections
+        minter_badges_issued: u64,
+
+        // Whether making new collections is temporarily halted
+        frozen: bool,
+
         // Closed date for the collection
         closed: Option<UtcDateTime>,
+
+        // Domain used for the NFTs before the last change of the base path
+        previous_domain: Option<String>,
     }
 
     impl Repository {
@@ -65,7 +167,19 @@ mod repository {
             base_path: String,
             repository_owner_access_badge_address: ResourceAddress,
             dapp_definition_address: GlobalAddress,
+            platform_fee_bps: u16,
+            url_template: String,
+            description: String,
         ) -> Global<Repository> {
+            let base_path = normalize_base_path(base_path);
+            let url_template = validate_url_template(url_template);
+            let description = validate_resource_description(description);
+
+            assert!(
+                platform_fee_bps <= 10000,
+                "Platform fee must not be over 10000 basis points."
+            );
+
             let (address_reservation, component_address) =
                 Runtime::allocate_component_address(Repository::blueprint_id());
 
@@ -93,6 +207,13 @@ mod repository {
                 withdrawer => rule!(deny_all);
                 withdrawer_updater => rule!(deny_all);
             })
+            // The minter badge can not be withdrawn, and is burned by the collection holding it when
+            // the collection is closed. Collections are not known up front, so burning is allowed
+            // to components of this package only.
+            .burn_roles(burn_roles! {
+                burner => rule!(require(package_of_direct_caller(Runtime::package_address())));
+                burner_updater => rule!(deny_all);
+            })
             .create_with_no_initial_supply();
 
             // Creating an collection owner badge for the trophy collections. This is used to set
@@ -127,7 +248,9 @@ mod repository {
 
             // Manager for minting trophies for a central collection. This manager will be handed
             // down to collection components together with a minter badge. This allows all
-            // collections to mint trophies from the same resource manager.
+            // collections to mint trophies from the same resource manager. Royalties can only be set
+            // on packages and components, not on resources, so transfers of trophies are fee free,
+            // and a fee on secondary sales has to be taken by the marketplace.
             let trophy_resource_manager = ResourceBuilder::new_ruid_non_fungible_with_registered_type::<Trophy>(OwnerRole::Fixed(
                 rule!(require(repository_owner_access_badge_address))
             ))
@@ -140,12 +263,13 @@ mod repository {
                     },
                     init {
                         "name" => "Backeum Trophies", locked;
-                        "description" => "Backeum trophies celebrates the patronage of its holder with donations to individual Backeum creators. A unique symbol of support for the community, it's a vibrant testament to financial encouragement.", locked;
+                        "description" => description, locked;
                         "domain" => base_path.clone(), updatable;
+                        "url_template" => url_template.clone(), locked;
                         "icon_url" => UncheckedUrl::of(format!("{}{}", base_path, "/bucket/assets/wallet-assets/trophy.png")), updatable;
                         "tags" => vec!["backeum", "trophy"], locked;
                         "info_url" => UncheckedUrl::of(base_path.clone()), locked;
-                        "dapp_definition" => dapp_definition_address, locked;
+                        "dapp_definition" => dapp_definition_address, updatable;
                     }
                 ))
                 .mint_roles(mint_roles!(
@@ -153,7 +277,7 @@ mod repository {
                     minter_updater => rule!(require(repository_owner_access_badge_address));
                 ))
                 .burn_roles(burn_roles!(
-                    burner => rule!(require(global_caller(component_address)));
+                    burner => rule!(require(minter_badge_manager.address()) || require(global_caller(component_address)));
                     burner_updater => rule!(require(repository_owner_access_badge_address));
                 ))
                 .non_fungible_data_update_roles(non_fungible_data_update_roles!(
@@ -240,7 +364,17 @@ mod repository {
                 minter_badge_manager,
                 repository_owner_access_badge_address,
                 dapp_definition_address,
+                platform_fee_bps,
+                collections: KeyValueStore::new(),
+                collection_slugs: KeyValueStore::new(),
+                collection_trophies: KeyValueStore::new(),
+                collection_trophy_counts: KeyValueStore::new(),
+                collection_totals: KeyValueStore::new(),
+                url_template,
+                minter_badges_issued: 0,
+                frozen: false,
                 closed: None,
+                previous_domain: None,
             }
             .instantiate()
             .prepare_to_globalize(OwnerRole::Fixed(
@@ -248,17 +382,18 @@ mod repository {
             ))
             .metadata(metadata!(
                 roles {
-                    metadata_setter => rule!(require(repository_owner_access_badge_address));
+                    metadata_setter => rule!(require(repository_owner_access_badge_address) || require(global_caller(component_address)));
                     metadata_setter_updater => rule!(require(repository_owner_access_badge_address));
                     metadata_locker => rule!(deny_all);
                     metadata_locker_updater => rule!(deny_all);
                 },
                 init {
-                    "dapp_definition" => dapp_definition_address, locked;
+                    "dapp_definition" => dapp_definition_address, updatable;
                 }
             ))
             .roles(roles! {
                 admin => rule!(require(repository_owner_access_badge_address));
+                minter => rule!(require(minter_badge_manager.address()));
             })
             .with_address(address_reservation)
             .globalize()
@@ -273,11 +408,16 @@ mod repository {
             creator_badge_proof: Proof,
             trophy_name: String,
             trophy_description: String,
+            accepted_resources: Vec<ResourceAddress>,
+            custom_info_url: Option<String>,
+            donation_cap: Option<Decimal>,
         ) -> Global<Collection> {
             if self.closed.is_some() {
                 panic!("This repository is permanently closed.");
             }
 
+            assert!(!self.frozen, "Repository is frozen");
+
             assert!(
                 trophy_name.len() >= 3,
                 "Trophy name must be 3 characters or more."
@@ -298,49 +438,95 @@ mod repository {
                 "Trophy description must not be over 255 characters"
             );
 
+            assert!(
+                !accepted_resources.is_empty(),
+                "At least one accepted resource must be given."
+            );
+
+            for resource in accepted_resources.iter() {
+                assert!(
+                    ResourceManager::from(*resource)
+                        .resource_type()
+                        .is_fungible(),
+                    "Accepted resources must be fungible resources."
+                );
+            }
+
+            let custom_info_url = custom_info_url.map(validate_info_url);
+
+            if let Some(donation_cap) = donation_cap {
+                assert!(donation_cap > dec!(0), "Donation cap must be over zero.");
+            }
+
             let checked_creator_badge_proof =
                 creator_badge_proof.check(self.creator_resource_manager.address());
 
-            let data: Creator = self.creator_resource_manager.get_non_fungible_data(
-                &checked_creator_badge_proof
-                    .as_non_fungible()
-                    .non_fungible_local_id(),
+            let creator_badge_local_id = checked_creator_badge_proof
+                .as_non_fungible()
+                .non_fungible_local_id();
+            let data: Creator = self
+                .creator_resource_manager
+                .get_non_fungible_data(&creator_badge_local_id);
+
+            self.claim_slug(
+                data.creator_slug.clone(),
+                NonFungibleGlobalId::new(
+                    self.creator_resource_manager.address(),
+                    creator_badge_local_id,
+                ),
             );
 
             let minter_badge = self.minter_badge_manager.mint(1);
+            self.minter_badges_issued += 1;
 
-            Collection::new(CollectionArg {
+            let collection = Collection::new(CollectionArg {
                 trophy_resource_manager: self.trophy_resource_manager,
                 thanks_token_resource_manager: self.thanks_token_resource_manager,
                 membership_resource_manager: self.membership_resource_manager,
                 repository_owner_access_badge_address: self.repository_owner_access_badge_address,
+                repository_address: Runtime::global_address(),
                 creator_resource_manager: self.creator_resource_manager,
                 creator_badge_proof: checked_creator_badge_proof.clone(),
+                team_badges: vec![],
                 minter_badge,
                 creator_name: data.creator_name,
-                creator_slug: data.creator_slug,
+                creator_slug: data.creator_slug.clone(),
                 trophy_name,
                 trophy_description,
+                accepted_resources,
+                custom_info_url,
+                donation_cap,
+                platform_fee_bps: self.platform_fee_bps,
                 dapp_definition_address: self.dapp_definition_address,
-            })
+            });
+
+            self.register_collection(collection, data.creator_slug)
         }
 
         // new_collection_component_and_badge sets up a new collection component for a user, and
         // give that contract a mint badge that allows for it to create and update trophies. By
         // going through Repository for instantiation we can ensure that the mint badge is only
         // given to a contract that is made by Backeum. This method also returns the collection
-        // owner badge that the user can use to gain ownership of the collection.
+        // owner badge that the user can use to gain ownership of the collection. For team run
+        // collections several copies of the creator badge can be minted with admin_count. All copies
+        // are equivalent admins of the collection, there is no permissioning per team member.
         pub fn new_collection_component_and_badge(
             &mut self,
             creator_name: String,
             creator_slug: String,
             trophy_name: String,
             trophy_description: String,
+            accepted_resources: Vec<ResourceAddress>,
+            custom_info_url: Option<String>,
+            donation_cap: Option<Decimal>,
+            admin_count: u8,
         ) -> (Global<Collection>, Bucket) {
             if self.closed.is_some() {
                 panic!("This repository is permanently closed.");
             }
 
+            assert!(!self.frozen, "Repository is frozen");
+
             assert_ne!(
                 creator_name.len(),
                 0,
@@ -352,16 +538,7 @@ mod repository {
                 "Creator name must not be over 255 characters"
             );
 
-            assert_ne!(
-                creator_slug.len(),
-                0,
-                "Creator slug must be 1 character or more."
-            );
-
-            assert!(
-                creator_slug.len() <= 30,
-                "Creator slug must not be over 30 characters"
-            );
+            let creator_slug = validate_slug(creator_slug);
 
             assert!(
                 trophy_name.len() >= 3,
@@ -383,59 +560,366 @@ mod repository {
                 "Trophy description must not be over 255 characters"
             );
 
-            // Get the domain name used from the trophy resource manager.
-            let domain: String = self
-                .trophy_resource_manager
-                .get_metadata("domain")
-                .unwrap()
-                .expect("No domain on NFT repository");
+            assert!(
+                !accepted_resources.is_empty(),
+                "At least one accepted resource must be given."
+            );
+
+            for resource in accepted_resources.iter() {
+                assert!(
+                    ResourceManager::from(*resource)
+                        .resource_type()
+                        .is_fungible(),
+                    "Accepted resources must be fungible resources."
+                );
+            }
+
+            let custom_info_url = custom_info_url.map(validate_info_url);
+
+            if let Some(donation_cap) = donation_cap {
+                assert!(donation_cap > dec!(0), "Donation cap must be over zero.");
+            }
+
+            assert!(admin_count >= 1, "At least one admin badge must be minted.");
+
+            let domain = self.domain();
 
             let created = generate_created_string(
                 UtcDateTime::from_instant(&Clock::current_time_rounded_to_minutes()).unwrap(),
             );
 
-            let creator_badge = self
+            let creator_data = Creator {
+                name: format!("Creator Badge: {}", creator_name.clone(),),
+                description:
+                    "Grants ownership of Backeum collection components and membership badges"
+                        .to_string(),
+                creator_name: creator_name.clone(),
+                creator_slug: creator_slug.clone(),
+                created: created.clone(),
+                funded: dec!(0),
+                key_image_url: UncheckedUrl::of(generate_creator_url(
+                    domain.to_string(),
+                    dec!(0),
+                    created,
+                )),
+            };
+
+            let mut creator_badge = self
                 .creator_resource_manager
-                .mint_ruid_non_fungible::<Creator>(Creator {
-                    name: format!("Creator Badge: {}", creator_name.clone(),),
-                    description:
-                        "Grants ownership of Backeum collection components and membership badges"
-                            .to_string(),
-                    creator_name: creator_name.clone(),
-                    creator_slug: creator_slug.clone(),
-                    created: created.clone(),
-                    funded: dec!(0),
-                    key_image_url: UncheckedUrl::of(generate_creator_url(
-                        domain.to_string(),
-                        dec!(0),
-                        created,
-                    )),
-                });
+                .mint_ruid_non_fungible::<Creator>(creator_data.clone());
+
+            self.claim_slug(
+                creator_slug.clone(),
+                NonFungibleGlobalId::new(
+                    self.creator_resource_manager.address(),
+                    creator_badge.as_non_fungible().non_fungible_local_id(),
+                ),
+            );
+
+            // Mint the copies of the creator badge for the rest of the team.
+            let mut team_badge_ids: Vec<NonFungibleGlobalId> = vec![];
+            let mut team_badges = Bucket::new(self.creator_resource_manager.address());
+            for _ in 1..admin_count {
+                let team_badge = self
+                    .creator_resource_manager
+                    .mint_ruid_non_fungible::<Creator>(creator_data.clone());
+                team_badge_ids.push(NonFungibleGlobalId::new(
+                    self.creator_resource_manager.address(),
+                    team_badge.as_non_fungible().non_fungible_local_id(),
+                ));
+                team_badges.put(team_badge);
+            }
 
             let minter_badge = self.minter_badge_manager.mint(1);
+            self.minter_badges_issued += 1;
+
+            let collection = Collection::new(CollectionArg {
+                trophy_resource_manager: self.trophy_resource_manager,
+                thanks_token_resource_manager: self.thanks_token_resource_manager,
+                membership_resource_manager: self.membership_resource_manager,
+                creator_resource_manager: self.creator_resource_manager,
+                repository_owner_access_badge_address: self.repository_owner_access_badge_address,
+                repository_address: Runtime::global_address(),
+                creator_badge_proof: creator_badge
+                    .create_proof_of_all()
+                    .check(self.creator_resource_manager.address()),
+                team_badges: team_badge_ids,
+                minter_badge,
+                creator_name,
+                creator_slug: creator_slug.clone(),
+                trophy_name,
+                trophy_description,
+                accepted_resources,
+                custom_info_url,
+                donation_cap,
+                platform_fee_bps: self.platform_fee_bps,
+                dapp_definition_address: self.dapp_definition_address,
+            });
+
+            creator_badge.put(team_badges);
 
             (
-                Collection::new(CollectionArg {
-                    trophy_resource_manager: self.trophy_resource_manager,
-                    thanks_token_resource_manager: self.thanks_token_resource_manager,
-                    membership_resource_manager: self.membership_resource_manager,
-                    creator_resource_manager: self.creator_resource_manager,
-                    repository_owner_access_badge_address: self
-                        .repository_owner_access_badge_address,
-                    creator_badge_proof: creator_badge
-                        .create_proof_of_all()
-                        .check(self.creator_resource_manager.address()),
-                    minter_badge,
-                    creator_name,
-                    creator_slug,
-                    trophy_name,
-                    trophy_description,
-                    dapp_definition_address: self.dapp_definition_address,
-                }),
+                self.register_collection(collection, creator_slug),
                 creator_badge,
             )
         }
 
+        // new_collection_and_donate sets up a new collection component and creator badge like
+        // new_collection_component_and_badge, and makes the first donation to it in the same
+        // transaction. The collection accepts the resource of the donation, and the usual fee of
+        // the collection is taken from it. Returns the collection, the trophy, the creator badge,
+        // the thanks tokens and the membership. The same package royalty as for making a collection
+        // is charged, as the collection is made without going through a royalty charged method.
+        pub fn new_collection_and_donate(
+            &mut self,
+            creator_name: String,
+            creator_slug: String,
+            trophy_name: String,
+            trophy_description: String,
+            tokens: Bucket,
+        ) -> (Global<Collection>, Bucket, Bucket, Bucket, Bucket) {
+            let (collection, creator_badge) = self.new_collection_component_and_badge(
+                creator_name,
+                creator_slug,
+                trophy_name,
+                trophy_description,
+                vec![tokens.resource_address()],
+                None,
+                None,
+                1,
+            );
+
+            let amount = tokens.amount();
+            let (trophy, thanks, membership) = collection.donate_mint_at_creation(tokens);
+
+            // The collection can not call back to the repository during this method, so the
+            // trophy and the total of the collection are recorded here.
+            let collection_id = Runtime::bech32_encode_address(collection.address());
+            self.add_to_trophy_index(
+                collection_id.clone(),
+                trophy.as_non_fungible().non_fungible_local_id(),
+            );
+            self.collection_totals.insert(collection_id, amount);
+
+            (collection, trophy, creator_badge, thanks, membership)
+        }
+
+        // domain is a private method that returns the domain name used for urls, as set in the
+        // metadata of the trophy resource manager.
+        fn domain(&self) -> String {
+            self.trophy_resource_manager
+                .get_metadata("domain")
+                .unwrap()
+                .expect("No domain on NFT repository")
+        }
+
+        // claim_slug is a private method that records the given creator badge as the creator of the
+        // given slug. The info url of trophies is based on the slug, so a slug can only be used by
+        // the collections of the creator badge that first made a collection with it.
+        fn claim_slug(&mut self, creator_slug: String, creator: NonFungibleGlobalId) {
+            if let Some(owner) = self.collection_slugs.get(&creator_slug) {
+                assert!(
+                    owner.eq(&creator),
+                    "The creator slug {} is already used by the collections of another creator, pick another slug.",
+                    creator_slug
+                );
+                return;
+            }
+
+            self.collection_slugs.insert(creator_slug, creator);
+        }
+
+        // claim_creator_slug is a method for the collections to claim a new creator slug for the
+        // given creator badge, when the creator changes it with update_profile. It requires a proof
+        // of the minter badge.
+        pub fn claim_creator_slug(&mut self, creator_slug: String, creator: NonFungibleGlobalId) {
+            self.claim_slug(creator_slug, creator);
+        }
+
+        // register_collection is a private method that records a newly made collection, so that
+        // it can be looked up by its collection id, and emits an event for indexers.
+        fn register_collection(
+            &mut self,
+            collection: Global<Collection>,
+            creator_slug: String,
+        ) -> Global<Collection> {
+            let collection_id = Runtime::bech32_encode_address(collection.address());
+            assert!(
+                self.collections.get(&collection_id).is_none(),
+                "A collection with the id {} is already registered.",
+                collection_id
+            );
+
+            self.collections
+                .insert(collection_id.clone(), collection.address());
+
+            Runtime::emit_event(CollectionCreated {
+                collection_id,
+                component: collection.address(),
+                creator_slug,
+                creator_badge_address: self.creator_resource_manager.address(),
+            });
+
+            collection
+        }
+
+        // get_collection returns the collection component address for the given collection id,
+        // if the collection was made by this repository.
+        pub fn get_collection(&self, collection_id: String) -> Option<ComponentAddress> {
+            self.collections
+                .get(&collection_id)
+                .map(|collection| *collection)
+        }
+
+        // get_trophy_resource_address returns the resource address of the trophies, so clients don't
+        // depend on the order of the resources made at instantiation.
+        pub fn get_trophy_resource_address(&self) -> ResourceAddress {
+            self.trophy_resource_manager.address()
+        }
+
+        // index_trophy is a method for the collections to register a newly minted trophy in the
+        // index of its collection, it requires a proof of the minter badge.
+        pub fn index_trophy(&mut self, nft_id: NonFungibleLocalId) {
+            let data: Trophy = self.trophy_resource_manager.get_non_fungible_data(&nft_id);
+            self.add_to_trophy_index(data.collection_id, nft_id);
+        }
+
+        // add_to_trophy_index is a private method that adds a trophy id to the index of the given
+        // collection id. Every id is stored in its own entry, so the index of a collection can grow
+        // without limit.
+        fn add_to_trophy_index(&mut self, collection_id: String, nft_id: NonFungibleLocalId) {
+            let count = self.get_collection_trophy_count(collection_id.clone());
+
+            self.collection_trophies
+                .insert((collection_id.clone(), count), nft_id);
+            self.collection_trophy_counts
+                .insert(collection_id, count + 1);
+        }
+
+        // get_collection_trophies returns a page of the ids of the trophies minted for the given
+        // collection id, in minting order, starting at the given offset. At most MAX_BATCH_SIZE ids
+        // are returned per call, so callers are expected to paginate using
+        // get_collection_trophy_count. Trophies that have been merged, split or burned are kept in
+        // the index, so callers should check that a trophy still exists.
+        pub fn get_collection_trophies(
+            &self,
+            collection_id: String,
+            offset: u64,
+            limit: u64,
+        ) -> Vec<NonFungibleLocalId> {
+            assert!(
+                limit <= MAX_BATCH_SIZE as u64,
+                "Can not get more than {} trophies at a time.",
+                MAX_BATCH_SIZE
+            );
+
+            let count = self.get_collection_trophy_count(collection_id.clone());
+            let end = count.min(offset.saturating_add(limit));
+
+            (offset..end)
+                .map(|position| {
+                    self.collection_trophies
+                        .get(&(collection_id.clone(), position))
+                        .unwrap()
+                        .clone()
+                })
+                .collect()
+        }
+
+        // get_collection_trophy_count returns the number of trophies in the index of the given
+        // collection id.
+        pub fn get_collection_trophy_count(&self, collection_id: String) -> u64 {
+            self.collection_trophy_counts
+                .get(&collection_id)
+                .map(|count| *count)
+                .unwrap_or_default()
+        }
+
+        // update_collection_total is a method for the collections to report their total donated
+        // after every donation and refund, it requires a proof of the minter badge.
+        pub fn update_collection_total(&mut self, collection_id: String, total: Decimal) {
+            assert!(
+                self.collections.get(&collection_id).is_some(),
+                "The given collection was not made by this repository."
+            );
+
+            self.collection_totals.insert(collection_id, total);
+        }
+
+        // get_collection_total returns the total donated to the given collection id, e.g. for a
+        // leaderboard. Collections report their total in the same transaction as the donation, so
+        // the total is always in line with the collection itself, at the cost of a call to the
+        // repository on every donation. Donations of all accepted resources are summed, in the
+        // same way as the total of the collection.
+        pub fn get_collection_total(&self, collection_id: String) -> Decimal {
+            self.collection_totals
+                .get(&collection_id)
+                .map(|total| *total)
+                .unwrap_or(dec!(0))
+        }
+
+        // get_minter_badges_issued returns the number of minter badges handed out to collections.
+        // Every collection holds a single minter badge, which is burned when it is revoked.
+        pub fn get_minter_badges_issued(&self) -> u64 {
+            self.minter_badges_issued
+        }
+
+        // get_trophy returns the data of the trophy with the given id.
+        pub fn get_trophy(&self, nft_id: NonFungibleLocalId) -> Trophy {
+            assert!(
+                self.trophy_resource_manager.non_fungible_exists(&nft_id),
+                "No trophy exists with the id {}.",
+                nft_id
+            );
+
+            self.trophy_resource_manager.get_non_fungible_data(&nft_id)
+        }
+
+        // filter_trophies returns the given trophy ids that have at least the given donated amount,
+        // in the given order. Trophies are held in accounts, so clients list the trophies of a
+        // wallet first and filter them here. Ids of trophies that no longer exist are left out. The
+        // number of ids per call is capped.
+        pub fn filter_trophies(
+            &self,
+            nft_ids: Vec<NonFungibleLocalId>,
+            min_donated: Decimal,
+        ) -> Vec<NonFungibleLocalId> {
+            assert!(
+                nft_ids.len() <= MAX_BATCH_SIZE,
+                "Can not filter more than {} trophies at a time.",
+                MAX_BATCH_SIZE
+            );
+
+            nft_ids
+                .into_iter()
+                .filter(|nft_id| {
+                    self.trophy_resource_manager.non_fungible_exists(nft_id)
+                        && self
+                            .trophy_resource_manager
+                            .get_non_fungible_data::<Trophy>(nft_id)
+                            .donated
+                            >= min_donated
+                })
+                .collect()
+        }
+
+        // export_trophy returns the trophy with the given id as a JSON object with a fixed field
+        // order, e.g. for off ledger backups and receipts. The donated amount is given as a string
+        // to keep the full precision of the Decimal.
+        pub fn export_trophy(&self, nft_id: NonFungibleLocalId) -> String {
+            let data = self.get_trophy(nft_id);
+
+            format!(
+                "{{\"name\":{},\"info_url\":{},\"collection_id\":{},\"created\":{},\"donated\":{},\"key_image_url\":{}}}",
+                json_string(&data.name),
+                json_string(data.info_url.as_str()),
+                json_string(&data.collection_id),
+                json_string(&data.created),
+                json_string(&data.donated.to_string()),
+                json_string(data.key_image_url.as_str())
+            )
+        }
+
         // Mints a new collection owner badge that the user can use to gain ownership of a
         // collection. Ownership badges are free to mint and burn.
         pub fn mint_creator_badge(&mut self, creator_name: String, creator_slug: String) -> Bucket {
@@ -450,23 +934,9 @@ mod repository {
                 "Creator name must not be over 255 characters"
             );
 
-            assert_ne!(
-                creator_slug.len(),
-                0,
-                "Creator slug must be 1 character or more."
-            );
-
-            assert!(
-                creator_slug.len() <= 30,
-                "Creator slug must not be over 30 characters"
-            );
+            let creator_slug = validate_slug(creator_slug);
 
-            // Get the domain name used from the trophy resource manager.
-            let domain: String = self
-                .trophy_resource_manager
-                .get_metadata("domain")
-                .unwrap()
-                .expect("No domain on NFT repository");
+            let domain = self.domain();
 
             let created = generate_created_string(
                 UtcDateTime::from_instant(&Clock::current_time_rounded_to_minutes()).unwrap(),
@@ -490,21 +960,131 @@ mod repository {
                 })
         }
 
+        // mint_replacement_creator_badge is a method for the collections to mint a copy of the given
+        // creator badge, when the creator has lost it. It requires a proof of the minter badge, and
+        // is only called by mint_replacement_admin_badge of the collection, which is restricted to
+        // the repository owner.
+        pub fn mint_replacement_creator_badge(
+            &mut self,
+            creator_badge: NonFungibleLocalId,
+        ) -> Bucket {
+            let data: Creator = self
+                .creator_resource_manager
+                .get_non_fungible_data(&creator_badge);
+
+            self.creator_resource_manager.mint_ruid_non_fungible(data)
+        }
+
         // merge_trophies will take multiple trophies of the same collection id and merge them into
-        // one.
+        // one. A merged event is emitted with the burned trophy ids and the resulting trophy, so
+        // indexers don't have to infer the burn from state. The merged trophy can not be refunded,
+        // as the collection can only tell what was deposited for the trophies it minted itself.
         pub fn merge_trophies(&mut self, trophies: Bucket) -> Bucket {
+            assert!(
+                !trophies.resource_address().is_fungible(),
+                "The given trophies are of fungible resource {}, expected trophies of {}.",
+                Runtime::bech32_encode_address(trophies.resource_address()),
+                Runtime::bech32_encode_address(self.trophy_resource_manager.address())
+            );
+
             assert_eq!(
                 trophies.resource_address(),
                 self.trophy_resource_manager.address(),
-                "The given trophies is not the of the same resource type as managed by the repository."
+                "The given trophies are of resource {}, expected trophies of {} as managed by the repository.",
+                Runtime::bech32_encode_address(trophies.resource_address()),
+                Runtime::bech32_encode_address(self.trophy_resource_manager.address())
+            );
+
+            assert!(
+                trophies.amount() >= dec!(2),
+                "At least two trophies must be given to merge, got {}.",
+                trophies.amount()
+            );
+
+            let trophies_list = trophies.as_non_fungible().non_fungibles::<Trophy>();
+            let merged_ids: Vec<NonFungibleLocalId> = trophies_list
+                .iter()
+                .map(|trophy| trophy.local_id().clone())
+                .collect();
+            let (template, donated, created, transactions) = self.combine_trophies(trophies_list);
+
+            let domain = self.domain();
+
+            let new_trophy_data = Trophy {
+                name: template.name,
+                description: template.description,
+                creator: template.creator,
+                creator_name: template.creator_name,
+                creator_slug: template.creator_slug,
+                info_url: template.info_url,
+                collection_id: template.collection_id.clone(),
+                currency: template.currency,
+                original_donor: None,
+                message: None,
+                is_anonymous: template.is_anonymous,
+                referrer: None,
+                created: created.clone(),
+                transactions,
+                donated,
+                refundable: false,
+                tier: compute_tier(donated),
+                key_image_url: UncheckedUrl::of(generate_trophy_url(
+                    &self.url_template,
+                    domain.to_string(),
+                    donated,
+                    created.clone(),
+                    template.collection_id.clone(),
+                    Runtime::bech32_encode_address(template.currency),
+                )),
+            };
+
+            // Burn the previous trophies.
+            trophies.burn();
+
+            let merged = self
+                .trophy_resource_manager
+                .mint_ruid_non_fungible(new_trophy_data.clone());
+            self.add_to_trophy_index(
+                new_trophy_data.collection_id.clone(),
+                merged.as_non_fungible().non_fungible_local_id(),
+            );
+
+            Runtime::emit_event(TrophyMerged {
+                collection_id: new_trophy_data.collection_id,
+                merged_count: merged_ids.len() as u64,
+                merged_ids,
+                total_donated: donated,
+                earliest_created: created,
+                result_id: merged.as_non_fungible().non_fungible_local_id(),
+            });
+
+            merged
+        }
+
+        // combine_trophies is a private method that checks that the given trophies can be merged,
+        // and returns the first trophy as template together with the summed donation, the earliest
+        // created date and the transactions of all trophies sorted from oldest to newest. The
+        // created date is of minute granularity if any of the trophies has a minute created date,
+        // and of day granularity only if all of them do, whatever the order of the trophies. If the
+        // combined transactions exceed MAX_TRANSACTIONS the oldest ones are dropped, the summed
+        // donation still includes them.
+        fn combine_trophies(
+            &self,
+            trophies_list: Vec<NonFungible<Trophy>>,
+        ) -> (Trophy, Decimal, String, Vec<Transaction>) {
+            assert!(!trophies_list.is_empty(), "No trophies given to merge.");
+
+            assert!(
+                trophies_list.len() <= MAX_MERGE_SIZE,
+                "Can not merge more than {} trophies at a time, split the merge into several transactions.",
+                MAX_MERGE_SIZE
             );
 
-            let non_fungible_bucket = trophies.as_non_fungible();
-            let trophies_list = non_fungible_bucket.non_fungibles::<Trophy>();
             let template = trophies_list.first().unwrap().data();
             let mut earliest_created: UtcDateTime =
                 UtcDateTime::from_instant(&Clock::current_time_rounded_to_minutes()).unwrap();
 
+            let mut granularity = CreatedGranularity::Day;
             let mut donated = dec!(0);
             let mut transactions: Vec<Transaction> = vec![];
             for trophy_data in trophies_list.iter() {
@@ -519,6 +1099,11 @@ mod repository {
                     "The given trophies is not the of the same info url."
                 );
 
+                assert_eq!(
+                    data.currency, template.currency,
+                    "The given trophies is not the of the same currency."
+                );
+
                 assert_eq!(
                     data.name, template.name,
                     "The given trophies is not the of the same name."
@@ -539,6 +1124,10 @@ mod repository {
                     "The given trophies is not the of the same creator slug."
                 );
 
+                if created_granularity_of(&data.created) == CreatedGranularity::Minute {
+                    granularity = CreatedGranularity::Minute;
+                }
+
                 let trophy_date = parse_created_string(data.created);
 
                 if trophy_date
@@ -549,55 +1138,214 @@ mod repository {
                 }
 
                 transactions.extend(data.transactions.clone());
-                donated += data.donated;
+                donated = add_donated(donated, data.donated);
             }
 
-            // Sort the transactions by created date.
-            transactions.sort_by(|a, b| {
-                let a_date = parse_created_string(a.created.clone());
-                let b_date = parse_created_string(b.created.clone());
-                if a_date
+            // Sort the transactions by created date. The sort is stable, so transactions with the
+            // same date keep the order of the given trophies.
+            transactions.sort_by_key(|transaction| {
+                parse_created_string(transaction.created.clone())
                     .to_instant()
-                    .compare(b_date.to_instant(), TimeComparisonOperator::Lt)
-                {
-                    return Ordering::Less;
+                    .seconds_since_unix_epoch
+            });
+            cap_transactions(&mut transactions);
+
+            let created = generate_created_string_with_granularity(earliest_created, &granularity);
+
+            (template, donated, created, transactions)
+        }
+
+        // preview_merge returns the donated total and created date that merging the given
+        // trophies would result in, without burning them.
+        pub fn preview_merge(&self, trophies: Proof) -> (Decimal, String) {
+            let checked_trophies = trophies.check(self.trophy_resource_manager.address());
+            let trophies_list = checked_trophies.as_non_fungible().non_fungibles::<Trophy>();
+            let (_, donated, created, _) = self.combine_trophies(trophies_list);
+
+            (donated, created)
+        }
+
+        // sum_donations returns the total donated of the given trophies, e.g. for the portfolio total
+        // of a wallet. Unlike merge_trophies the trophies are not burned, and may be of any
+        // collection.
+        pub fn sum_donations(&self, trophies: Proof) -> Decimal {
+            let checked_trophies = trophies.check(self.trophy_resource_manager.address());
+
+            checked_trophies
+                .as_non_fungible()
+                .non_fungibles::<Trophy>()
+                .iter()
+                .fold(dec!(0), |total, trophy| {
+                    add_donated(total, trophy.data().donated)
+                })
+        }
+
+        // earliest_created returns the earliest created date of the given trophies, as the created
+        // string of that trophy. This is a cheaper alternative to preview_merge when only the date
+        // is needed, note that it does not check that the trophies are of the same collection.
+        pub fn earliest_created(&self, trophies: Proof) -> String {
+            let checked_trophies = trophies.check(self.trophy_resource_manager.address());
+            let trophies_list = checked_trophies.as_non_fungible().non_fungibles::<Trophy>();
+
+            trophies_list
+                .iter()
+                .map(|trophy| trophy.data().created)
+                .min_by_key(|created| {
+                    parse_created_string(created.clone())
+                        .to_instant()
+                        .seconds_since_unix_epoch
+                })
+                .expect("No trophies given.")
+        }
+
+        // merge_trophies_grouped will take trophies of any number of collections, and merge the
+        // trophies of each collection into one. A single trophy is returned per collection.
+        pub fn merge_trophies_grouped(&mut self, trophies: Bucket) -> Bucket {
+            assert_eq!(
+                trophies.resource_address(),
+                self.trophy_resource_manager.address(),
+                "The given trophies is not the of the same resource type as managed by the repository."
+            );
+
+            assert!(!trophies.is_empty(), "No trophies given to merge.");
+
+            assert!(
+                trophies.amount() <= Decimal::from(MAX_MERGE_SIZE as u64),
+                "Can not merge more than {} trophies at a time, split the merge into several transactions.",
+                MAX_MERGE_SIZE
+            );
+
+            // Group the trophies by collection id.
+            let mut groups: IndexMap<String, IndexSet<NonFungibleLocalId>> = index_map_new();
+            for trophy in trophies.as_non_fungible().non_fungibles::<Trophy>().iter() {
+                groups
+                    .entry(trophy.data().collection_id)
+                    .or_insert_with(index_set_new)
+                    .insert(trophy.local_id().clone());
+            }
+
+            let mut merged = Bucket::new(self.trophy_resource_manager.address());
+            for (_, nft_ids) in groups.iter() {
+                let group: Bucket = trophies
+                    .as_non_fungible()
+                    .take_non_fungibles(nft_ids)
+                    .into();
+
+                if nft_ids.len() > 1 {
+                    merged.put(self.merge_trophies(group));
+                } else {
+                    merged.put(group);
                 }
+            }
 
-                Ordering::Greater
-            });
+            trophies.drop_empty();
+            merged
+        }
 
-            // Get the domain name used from the trophy resource manager.
-            let domain: String = self
-                .trophy_resource_manager
-                .get_metadata("domain")
-                .unwrap()
-                .expect("No domain on NFT repository");
+        // split_trophy will take a single trophy and split it into multiple trophies of the same
+        // collection id, where the donated amounts of the new trophies add up to the original. A
+        // trophy can be split into at most MAX_MERGE_SIZE trophies, the most that can be merged
+        // back into one. Like merged trophies, the new trophies can not be refunded.
+        pub fn split_trophy(&mut self, trophy: Bucket, amounts: Vec<Decimal>) -> Bucket {
+            assert_eq!(
+                trophy.resource_address(),
+                self.trophy_resource_manager.address(),
+                "The given trophy is not the of the same resource type as managed by the repository."
+            );
 
-            let created = generate_created_string(earliest_created);
-            let new_trophy_data = Trophy {
-                name: template.name,
-                description: template.description,
-                creator: template.creator,
-                creator_name: template.creator_name,
-                creator_slug: template.creator_slug,
-                info_url: template.info_url,
-                collection_id: template.collection_id.clone(),
-                created: created.clone(),
-                transactions,
-                donated,
-                key_image_url: UncheckedUrl::of(generate_trophy_url(
-                    domain.to_string(),
-                    donated,
-                    created.clone(),
-                    template.collection_id.clone(),
-                )),
-            };
+            assert_eq!(
+                trophy.amount(),
+                dec!(1),
+                "Only a single trophy can be split at a time."
+            );
 
-            // Burn the previous trophies.
-            trophies.burn();
+            assert!(
+                amounts.len() >= 2,
+                "A trophy must be split into 2 or more trophies."
+            );
 
-            self.trophy_resource_manager
-                .mint_ruid_non_fungible(new_trophy_data.clone())
+            assert!(
+                amounts.len() <= MAX_MERGE_SIZE,
+                "Can not split a trophy into more than {} trophies at a time.",
+                MAX_MERGE_SIZE
+            );
+
+            let data: Trophy = trophy.as_non_fungible().non_fungible::<Trophy>().data();
+
+            let mut total = dec!(0);
+            for amount in amounts.iter() {
+                assert!(*amount > dec!(0), "Split amounts must be over zero.");
+                total = add_donated(total, *amount);
+            }
+
+            assert_eq!(
+                total, data.donated,
+                "The split amounts must add up to the donated amount of the trophy."
+            );
+
+            let domain = self.domain();
+
+            // Burn the previous trophy.
+            trophy.burn();
+
+            // Mint a new trophy for each amount, all carrying the created date of the original.
+            let mut shards: Vec<Bucket> = amounts
+                .into_iter()
+                .map(|amount| {
+                    self.trophy_resource_manager.mint_ruid_non_fungible(Trophy {
+                        transactions: vec![Transaction {
+                            amount,
+                            created: data.created.clone(),
+                        }],
+                        donated: amount,
+                        refundable: false,
+                        tier: compute_tier(amount),
+                        key_image_url: UncheckedUrl::of(generate_trophy_url(
+                            &self.url_template,
+                            domain.to_string(),
+                            amount,
+                            data.created.clone(),
+                            data.collection_id.clone(),
+                            Runtime::bech32_encode_address(data.currency),
+                        )),
+                        ..data.clone()
+                    })
+                })
+                .collect();
+
+            for shard in shards.iter() {
+                self.add_to_trophy_index(
+                    data.collection_id.clone(),
+                    shard.as_non_fungible().non_fungible_local_id(),
+                );
+            }
+
+            let mut trophies = shards.remove(0);
+            for shard in shards {
+                trophies.put(shard);
+            }
+
+            trophies
+        }
+
+        // burn_trophy is a method for the holders to permanently destroy their trophies. A burned
+        // event is emitted for each trophy so the donated amounts can be accounted for.
+        pub fn burn_trophy(&mut self, trophy: Bucket) {
+            assert_eq!(
+                trophy.resource_address(),
+                self.trophy_resource_manager.address(),
+                "The given trophy is not the of the same resource type as managed by the repository."
+            );
+
+            for trophy_data in trophy.as_non_fungible().non_fungibles::<Trophy>().iter() {
+                let data = trophy_data.data();
+                Runtime::emit_event(TrophyBurned {
+                    collection_id: data.collection_id,
+                    donated: data.donated,
+                });
+            }
+
+            trophy.burn();
         }
 
         // merge_memberships will take multiple memberships of the same creator and merge them into
@@ -654,29 +1402,19 @@ mod repository {
                 }
 
                 transactions.extend(data.transactions.clone());
-                donated += data.donated;
+                donated = add_donated(donated, data.donated);
             }
 
-            // Sort the transactions by created date.
-            transactions.sort_by(|a, b| {
-                let a_date = parse_created_string(a.created.clone());
-                let b_date = parse_created_string(b.created.clone());
-                if a_date
+            // Sort the transactions by created date. The sort is stable, so transactions with the
+            // same date keep the order of the given trophies.
+            transactions.sort_by_key(|transaction| {
+                parse_created_string(transaction.created.clone())
                     .to_instant()
-                    .compare(b_date.to_instant(), TimeComparisonOperator::Lt)
-                {
-                    return Ordering::Less;
-                }
-
-                Ordering::Greater
+                    .seconds_since_unix_epoch
             });
+            cap_transactions(&mut transactions);
 
-            // Get the domain name used from the trophy resource manager.
-            let domain: String = self
-                .trophy_resource_manager
-                .get_metadata("domain")
-                .unwrap()
-                .expect("No domain on NFT repository");
+            let domain = self.domain();
 
             let created = generate_created_string(earliest_created);
             let new_membership_data = Membership {
@@ -709,6 +1447,293 @@ mod repository {
             self.thanks_token_resource_manager.burn(thanks_token);
         }
 
+        // update_trophy_names is a method for the repository admin to rename the given trophies.
+        // The number of trophies per call is capped, so larger collections must be updated in
+        // batches over several transactions.
+        pub fn update_trophy_names(&mut self, nft_ids: Vec<NonFungibleLocalId>, new_name: String) {
+            assert!(
+                nft_ids.len() <= MAX_BATCH_SIZE,
+                "Can not update more than {} trophies at a time.",
+                MAX_BATCH_SIZE
+            );
+
+            assert!(
+                new_name.len() >= 3,
+                "Trophy name must be 3 characters or more."
+            );
+
+            assert!(
+                new_name.len() <= 32,
+                "Trophy name must not be over 32 characters"
+            );
+
+            for nft_id in nft_ids.iter() {
+                self.trophy_resource_manager.update_non_fungible_data(
+                    nft_id,
+                    "name",
+                    new_name.clone(),
+                );
+            }
+        }
+
+        // remap_collection_id is a method for the repository admin to move the given trophies to
+        // another collection made by this repository, e.g. when the collection of a creator is
+        // recreated under a new id. The image url of every trophy is regenerated, and the trophies
+        // are added to the trophy index of the new collection. The trophies must be of the creator
+        // and a currency of the new collection, so they can still be updated by it. The donations
+        // stay in the old collection, so moved trophies can not be refunded. The number of trophies
+        // per call is capped, so larger collections must be moved in batches over several
+        // transactions.
+        pub fn remap_collection_id(
+            &mut self,
+            nft_ids: Vec<NonFungibleLocalId>,
+            new_collection_id: String,
+        ) {
+            assert!(
+                nft_ids.len() <= MAX_BATCH_SIZE,
+                "Can not update more than {} trophies at a time.",
+                MAX_BATCH_SIZE
+            );
+
+            let collection: Global<Collection> = self
+                .collections
+                .get(&new_collection_id)
+                .map(|component| *component)
+                .unwrap_or_else(|| {
+                    panic!(
+                        "The collection {} was not made by this repository.",
+                        new_collection_id
+                    )
+                })
+                .into();
+            let info = collection.get_collection_info();
+            let accepted_resources = collection.get_accepted_resources();
+
+            let domain = self.domain();
+
+            for nft_id in nft_ids.iter() {
+                let data: Trophy = self.trophy_resource_manager.get_non_fungible_data(nft_id);
+
+                assert!(
+                    data.creator.eq(&info.creator),
+                    "The trophy {} is not of the creator of the collection {}.",
+                    nft_id,
+                    new_collection_id
+                );
+
+                assert!(
+                    accepted_resources.contains(&data.currency),
+                    "The trophy {} is of the currency {}, which is not accepted by the collection {}.",
+                    nft_id,
+                    Runtime::bech32_encode_address(data.currency),
+                    new_collection_id
+                );
+
+                self.trophy_resource_manager
+                    .update_non_fungible_data(nft_id, "refundable", false);
+
+                self.trophy_resource_manager.update_non_fungible_data(
+                    nft_id,
+                    "collection_id",
+                    new_collection_id.clone(),
+                );
+
+                self.trophy_resource_manager.update_non_fungible_data(
+                    nft_id,
+                    "key_image_url",
+                    UncheckedUrl::of(generate_trophy_url(
+                        &self.url_template,
+                        domain.clone(),
+                        data.donated,
+                        data.created,
+                        new_collection_id.clone(),
+                        Runtime::bech32_encode_address(data.currency),
+                    )),
+                );
+
+                self.add_to_trophy_index(new_collection_id.clone(), nft_id.clone());
+            }
+        }
+
+        // update_base_path is a method for the repository admin to change the domain used for the
+        // NFTs, and regenerate the image url and info url of the given trophies. Only info urls in
+        // the default form of the previous base path followed by /p/ and the creator slug are
+        // rewritten, custom info urls of a collection are kept even when they end the same way. The
+        // number of trophies per call is capped, so callers are expected to paginate over the
+        // trophies of larger collections using several transactions with the same base path, the
+        // previous base path is remembered between them. Returns the number of trophies updated.
+        pub fn update_base_path(
+            &mut self,
+            new_base_path: String,
+            update_nft_ids: Vec<NonFungibleLocalId>,
+        ) -> u64 {
+            assert!(
+                update_nft_ids.len() <= MAX_BATCH_SIZE,
+                "Can not update more than {} trophies at a time.",
+                MAX_BATCH_SIZE
+            );
+
+            let new_base_path = normalize_base_path(new_base_path);
+
+            let old_domain = self.domain();
+            if old_domain != new_base_path {
+                self.previous_domain = Some(old_domain);
+                self.trophy_resource_manager
+                    .set_metadata("domain", new_base_path.clone());
+            }
+
+            for nft_id in update_nft_ids.iter() {
+                let data: Trophy = self.trophy_resource_manager.get_non_fungible_data(nft_id);
+
+                self.trophy_resource_manager.update_non_fungible_data(
+                    nft_id,
+                    "key_image_url",
+                    UncheckedUrl::of(generate_trophy_url(
+                        &self.url_template,
+                        new_base_path.clone(),
+                        data.donated,
+                        data.created,
+                        data.collection_id,
+                        Runtime::bech32_encode_address(data.currency),
+                    )),
+                );
+
+                let is_default_info_url = self.previous_domain.as_ref().is_some_and(|domain| {
+                    data.info_url.as_str() == format!("{}/p/{}", domain, data.creator_slug)
+                });
+                if is_default_info_url {
+                    self.trophy_resource_manager.update_non_fungible_data(
+                        nft_id,
+                        "info_url",
+                        UncheckedUrl::of(format!("{}/p/{}", new_base_path, data.creator_slug)),
+                    );
+                }
+            }
+
+            update_nft_ids.len() as u64
+        }
+
+        // migrate_image_urls is a method for the repository admin to regenerate the image url of the
+        // given trophies in the latest url format, e.g. after a query parameter was added. Unlike
+        // update_base_path the domain is kept. The number of trophies per call is capped, so
+        // callers are expected to paginate over the trophies using several transactions. Returns
+        // the number of trophies migrated.
+        pub fn migrate_image_urls(&mut self, nft_ids: Vec<NonFungibleLocalId>) -> u64 {
+            assert!(
+                nft_ids.len() <= MAX_BATCH_SIZE,
+                "Can not update more than {} trophies at a time.",
+                MAX_BATCH_SIZE
+            );
+
+            let domain = self.domain();
+
+            for nft_id in nft_ids.iter() {
+                let data: Trophy = self.trophy_resource_manager.get_non_fungible_data(nft_id);
+
+                self.trophy_resource_manager.update_non_fungible_data(
+                    nft_id,
+                    "key_image_url",
+                    UncheckedUrl::of(generate_trophy_url(
+                        &self.url_template,
+                        domain.clone(),
+                        data.donated,
+                        data.created,
+                        data.collection_id,
+                        Runtime::bech32_encode_address(data.currency),
+                    )),
+                );
+            }
+
+            Runtime::emit_event(ImageUrlsMigrated {
+                migrated_count: nft_ids.len() as u64,
+            });
+
+            nft_ids.len() as u64
+        }
+
+        // revoke_collection_minter is a method for the repository admin to revoke the minter badge
+        // of a compromised collection. Collections trust the repository that made them to revoke
+        // their minter badge, and only accept this call from that repository. The repository in
+        // turn trusts every collection it made with a minter badge for the shared trophy resource,
+        // until the badge is revoked.
+        pub fn revoke_collection_minter(&mut self, collection: ComponentAddress) {
+            let collection_id = Runtime::bech32_encode_address(collection);
+            assert!(
+                self.collections.get(&collection_id).is_some(),
+                "The given collection was not made by this repository."
+            );
+
+            let collection: Global<Collection> = collection.into();
+            collection.revoke_minter();
+        }
+
+        // deregister_collection is a method for the repository admin to remove a collection from
+        // the registry, e.g. after it was closed, so that get_collection no longer resolves to it.
+        // The collection itself is left as is, but the repository no longer accepts trophies and
+        // totals from it. The minter badge of a collection that is still open should therefore be
+        // revoked with revoke_collection_minter before it is deregistered. The trophy index and
+        // total of the collection are kept.
+        pub fn deregister_collection(&mut self, collection_id: String) {
+            let component = self
+                .collections
+                .remove(&collection_id)
+                .expect("The given collection was not made by this repository.");
+
+            Runtime::emit_event(CollectionDeregistered {
+                collection_id,
+                component,
+            });
+        }
+
+        // update_dapp_definition is a method for the repository admin to point the repository and
+        // the trophy resource to a new dapp definition. Collections made after this call use the
+        // new dapp definition, while existing collections are updated with
+        // update_collections_dapp_definition. The dapp definition of the creator badge, thanks
+        // token and membership resources remain immutable.
+        pub fn update_dapp_definition(&mut self, new_address: GlobalAddress) {
+            self.dapp_definition_address = new_address;
+
+            Runtime::global_component().set_metadata("dapp_definition", new_address);
+            self.trophy_resource_manager
+                .set_metadata("dapp_definition", new_address);
+        }
+
+        // get_dapp_definition returns the dapp definition the repository is associated with.
+        pub fn get_dapp_definition(&self) -> GlobalAddress {
+            self.dapp_definition_address
+        }
+
+        // update_collections_dapp_definition is a method for the repository admin to point the
+        // given collections to the current dapp definition of the repository. The number of
+        // collections per call is capped, so larger updates must be done in batches.
+        pub fn update_collections_dapp_definition(&mut self, collections: Vec<ComponentAddress>) {
+            assert!(
+                collections.len() <= MAX_BATCH_SIZE,
+                "Can not update more than {} collections at a time.",
+                MAX_BATCH_SIZE
+            );
+
+            for collection in collections {
+                let collection_id = Runtime::bech32_encode_address(collection);
+                assert!(
+                    self.collections.get(&collection_id).is_some(),
+                    "The given collection was not made by this repository."
+                );
+
+                let collection: Global<Collection> = collection.into();
+                collection.update_dapp_definition(self.dapp_definition_address);
+            }
+        }
+
+        // set_frozen is a method for the repository admin to halt or resume the making of new
+        // collections in an emergency. Existing collections keep operating independently, and
+        // trophies that are already minted are not affected by freezing.
+        pub fn set_frozen(&mut self, frozen: bool) {
+            self.frozen = frozen;
+
+            Runtime::emit_event(RepositoryFrozen { frozen });
+        }
+
         // close_repository is a method for the repository admin to close the repository
         // permanently. This will prevent any further collections to be made from the repository,
         // and will prevent any further usage of this repository.
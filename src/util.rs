@@ -1,16 +1,268 @@
+use crate::data::{CreatedGranularity, FeeMode, Transaction};
 use scrypto::prelude::*;
 
-// function to generate the url for the image
+// Version of the collection blueprint logic, stored on every collection at instantiation so
+// off-chain tooling can tell which capabilities a collection has.
+pub const COLLECTION_VERSION: u32 = 1;
+
+// Maximum number of NFTs that can be updated in a single batch call.
+pub const MAX_BATCH_SIZE: usize = 100;
+
+// Name displayed on trophies of donors that chose to be anonymous.
+pub const ANONYMOUS_TROPHY_NAME: &str = "Backeum Supporter";
+
+// Default number of minutes after a donation in which it can be refunded.
+pub const DEFAULT_REFUND_GRACE_PERIOD_MINUTES: i64 = 10;
+
+// Maximum number of characters in a donor message.
+pub const MAX_MESSAGE_LENGTH: usize = 280;
+
+// Maximum number of characters in a referrer.
+pub const MAX_REFERRER_LENGTH: usize = 64;
+
+// Maximum number of characters in a custom metadata key of a collection.
+pub const MAX_CUSTOM_METADATA_KEY_LENGTH: usize = 64;
+
+// Maximum number of characters in a custom metadata value of a collection.
+pub const MAX_CUSTOM_METADATA_VALUE_LENGTH: usize = 1024;
+
+// Maximum number of trophies that can be merged in a single call.
+pub const MAX_MERGE_SIZE: usize = 50;
+
+// Maximum percentage fee in basis points that can be taken from a donation.
+pub const MAX_PERCENTAGE_FEE_BPS: u16 = 1000;
+
+// Maximum flat fee that can be taken from a donation, in units of the donated resource.
+pub const MAX_FLAT_FEE: i64 = 100;
+
+// Maximum number of transactions kept on a single trophy or membership, to keep the NFT data
+// within size limits. The donated total still includes the transactions that are dropped.
+pub const MAX_TRANSACTIONS: usize = 50;
+
+// cap_transactions is a function that drops the oldest transactions, so that at most
+// MAX_TRANSACTIONS are kept. The transactions are expected to be sorted from oldest to newest.
+pub fn cap_transactions(transactions: &mut Vec<Transaction>) {
+    if transactions.len() > MAX_TRANSACTIONS {
+        transactions.drain(..transactions.len() - MAX_TRANSACTIONS);
+    }
+}
+
+// Donated amount from which a trophy reaches the silver tier.
+pub const SILVER_TIER_THRESHOLD: i64 = 100;
+
+// Donated amount from which a trophy reaches the gold tier.
+pub const GOLD_TIER_THRESHOLD: i64 = 1000;
+
+// compute_tier is a function that gives the tier of a trophy based on the amount donated.
+pub fn compute_tier(donated: Decimal) -> String {
+    if donated >= Decimal::from(GOLD_TIER_THRESHOLD) {
+        "gold".to_string()
+    } else if donated >= Decimal::from(SILVER_TIER_THRESHOLD) {
+        "silver".to_string()
+    } else {
+        "bronze".to_string()
+    }
+}
+
+// add_donated is a function that adds an amount to a donated total. It panics with a
+// descriptive message instead of the generic Decimal overflow when the total is too large.
+pub fn add_donated(donated: Decimal, amount: Decimal) -> Decimal {
+    donated
+        .checked_add(amount)
+        .expect("Donation total overflow")
+}
+
+// compute_fee is a function that gives the fee taken from a donation of the given amount. A flat
+// fee is capped at the donated amount, so a donation never goes below zero.
+pub fn compute_fee(fee_mode: &FeeMode, amount: Decimal) -> Decimal {
+    match fee_mode {
+        FeeMode::Flat(fee) => {
+            if *fee > amount {
+                amount
+            } else {
+                *fee
+            }
+        }
+        FeeMode::Percentage(bps) => amount * Decimal::from(*bps) / dec!(10000),
+    }
+}
+
+// validate_fee_mode is a function that rejects fee modes that would break the fee math, a
+// negative flat fee, and fee modes above MAX_FLAT_FEE or MAX_PERCENTAGE_FEE_BPS that would take
+// most of a donation.
+pub fn validate_fee_mode(fee_mode: &FeeMode) {
+    match fee_mode {
+        FeeMode::Flat(fee) => {
+            assert!(*fee >= dec!(0), "Flat fee must not be negative.");
+            assert!(
+                *fee <= Decimal::from(MAX_FLAT_FEE),
+                "Flat fee must not be over {}.",
+                MAX_FLAT_FEE
+            );
+        }
+        FeeMode::Percentage(bps) => assert!(
+            *bps <= MAX_PERCENTAGE_FEE_BPS,
+            "Percentage fee must not be over {} basis points.",
+            MAX_PERCENTAGE_FEE_BPS
+        ),
+    }
+}
+
+// round_to_divisibility is a function that rounds an amount towards zero to the given
+// divisibility of a resource, as buckets can not hold amounts more precise than the divisibility.
+// Rounding towards zero means fees computed from a donation are never rounded up.
+pub fn round_to_divisibility(amount: Decimal, divisibility: u8) -> Decimal {
+    amount
+        .checked_round(divisibility as i32, RoundingMode::ToZero)
+        .expect("Failed to round the amount to the divisibility of the resource.")
+}
+
+// normalize_base_path is a function that validates the base path used for metadata and image
+// urls, and trims any trailing slash so that urls are never generated with a double slash.
+pub fn normalize_base_path(base_path: String) -> String {
+    let trimmed = base_path.trim_end_matches('/');
+    let host = trimmed
+        .strip_prefix("https://")
+        .or_else(|| trimmed.strip_prefix("http://"))
+        .unwrap_or_else(|| {
+            panic!(
+                "Invalid base path {}, it must start with https:// or http://.",
+                base_path
+            )
+        });
+
+    assert!(
+        !host.is_empty(),
+        "Invalid base path {}, it must contain a host.",
+        base_path
+    );
+
+    trimmed.to_string()
+}
+
+// validate_info_url is a function that validates a custom info url given by a creator, which must
+// be an absolute http or https url with a host and no whitespace.
+pub fn validate_info_url(info_url: String) -> String {
+    let host = info_url
+        .strip_prefix("https://")
+        .or_else(|| info_url.strip_prefix("http://"))
+        .unwrap_or_else(|| {
+            panic!(
+                "Invalid info url {}, it must start with https:// or http://.",
+                info_url
+            )
+        });
+
+    assert!(
+        !host.is_empty() && !host.starts_with('/'),
+        "Invalid info url {}, it must contain a host.",
+        info_url
+    );
+
+    assert!(
+        !info_url.contains(char::is_whitespace),
+        "Invalid info url {}, it must not contain whitespace.",
+        info_url
+    );
+
+    info_url
+}
+
+// validate_slug is a function that validates a creator slug, which is used in urls and must
+// therefore be 1 to 30 characters of only lowercase ascii letters, digits and dashes.
+pub fn validate_slug(slug: String) -> String {
+    assert_ne!(slug.len(), 0, "Creator slug must be 1 character or more.");
+
+    assert!(
+        slug.len() <= 30,
+        "Creator slug must not be over 30 characters"
+    );
+
+    assert!(
+        slug.chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-'),
+        "Invalid creator slug {}, it may only contain lowercase letters, digits and dashes.",
+        slug
+    );
+
+    slug
+}
+
+// json_string is a function that quotes a value as a JSON string, escaping quotes, backslashes
+// and control characters.
+pub fn json_string(value: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+// Default description of the trophy resource.
+pub const DEFAULT_TROPHY_RESOURCE_DESCRIPTION: &str = "Backeum trophies celebrates the patronage of its holder with donations to individual Backeum creators. A unique symbol of support for the community, it's a vibrant testament to financial encouragement.";
+
+// validate_resource_description is a function that validates the description of the trophy
+// resource, which must be 1 to 255 characters.
+pub fn validate_resource_description(description: String) -> String {
+    assert_ne!(
+        description.len(),
+        0,
+        "Resource description must be 1 character or more."
+    );
+
+    assert!(
+        description.len() <= 255,
+        "Resource description must not be over 255 characters"
+    );
+
+    description
+}
+
+// Default template for the image url of trophies. The {base}, {collection}, {donated},
+// {created}, {currency} and {tier} placeholders are replaced when the url is generated.
+pub const DEFAULT_TROPHY_URL_TEMPLATE: &str =
+    "{base}/nft/collection/{collection}?donated={donated}&created={created}&currency={currency}&tier={tier}";
+
+// validate_url_template is a function that validates a trophy image url template, which must
+// contain the {base}, {collection}, {donated} and {created} placeholders.
+pub fn validate_url_template(url_template: String) -> String {
+    for placeholder in ["{base}", "{collection}", "{donated}", "{created}"] {
+        assert!(
+            url_template.contains(placeholder),
+            "Invalid url template {}, it must contain the {} placeholder.",
+            url_template,
+            placeholder
+        );
+    }
+
+    url_template
+}
+
+// function to generate the url for the image from the url template
 pub fn generate_trophy_url(
+    url_template: &str,
     base_path: String,
     donated: Decimal,
     created: String,
     collection_id: String,
+    currency: String,
 ) -> String {
-    format!(
-        "{}/nft/collection/{}?donated={}&created={}",
-        base_path, collection_id, donated, created
-    )
+    url_template
+        .replace("{base}", &base_path)
+        .replace("{collection}", &collection_id)
+        .replace("{donated}", &donated.to_string())
+        .replace("{created}", &created)
+        .replace("{currency}", &currency)
+        .replace("{tier}", &compute_tier(donated))
 }
 
 // function to generate the url for the image
@@ -34,45 +286,91 @@ pub fn generate_creator_url(base_path: String, donated: Decimal, created: String
     )
 }
 
-// function to generate the created string with a date format
+// function to generate the created string with a zero padded ISO-8601 date time format,
+// YYYY-MM-DDTHH:MM. Seconds are left out as the clock is rounded to minutes.
 pub fn generate_created_string(time: UtcDateTime) -> String {
-    let mut month = time.month().to_string();
-    match time.month() {
-        1 => month = "01".to_owned(),
-        2 => month = "02".to_owned(),
-        3 => month = "03".to_owned(),
-        4 => month = "04".to_owned(),
-        5 => month = "05".to_owned(),
-        6 => month = "06".to_owned(),
-        7 => month = "07".to_owned(),
-        8 => month = "08".to_owned(),
-        9 => month = "09".to_owned(),
-        _ => {}
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}",
+        time.year(),
+        time.month(),
+        time.day_of_month(),
+        time.hour(),
+        time.minute()
+    )
+}
+
+// generate_created_string_with_granularity is a function to generate the created string in the
+// given granularity, YYYY-MM-DDTHH:MM for minutes and YYYY-MM-DD for days.
+pub fn generate_created_string_with_granularity(
+    time: UtcDateTime,
+    granularity: &CreatedGranularity,
+) -> String {
+    match granularity {
+        CreatedGranularity::Minute => generate_created_string(time),
+        CreatedGranularity::Day => format!(
+            "{:04}-{:02}-{:02}",
+            time.year(),
+            time.month(),
+            time.day_of_month()
+        ),
     }
-    let mut day = time.day_of_month().to_string();
-    match time.day_of_month() {
-        1 => day = "01".to_owned(),
-        2 => day = "02".to_owned(),
-        3 => day = "03".to_owned(),
-        4 => day = "04".to_owned(),
-        5 => day = "05".to_owned(),
-        6 => day = "06".to_owned(),
-        7 => day = "07".to_owned(),
-        8 => day = "08".to_owned(),
-        9 => day = "09".to_owned(),
-        _ => {}
+}
+
+// created_granularity_of is a function that gives the granularity of an existing created string,
+// strings without a time are of day granularity.
+pub fn created_granularity_of(created: &str) -> CreatedGranularity {
+    match created.contains('T') {
+        true => CreatedGranularity::Minute,
+        false => CreatedGranularity::Day,
     }
-    format!("{}-{}-{}", time.year(), month, day)
 }
 
-// parse_created_string is a function that makes created string into a UtcDateTime
+// parse_created_string is a function that makes created string into a UtcDateTime. It accepts
+// both the date time format YYYY-MM-DDTHH:MM and the older date only format YYYY-MM-DD, which
+// is read as midnight. It panics with a descriptive message if the string is not valid.
 pub fn parse_created_string(input: String) -> UtcDateTime {
-    let mut split = input.split('-');
-    let year = split.next().unwrap();
-    let month = split.next().unwrap();
-    let day = split.next().unwrap();
-    let year_int = year.parse::<u32>().unwrap();
-    let month_int = month.parse::<u8>().unwrap();
-    let day_int = day.parse::<u8>().unwrap();
-    UtcDateTime::new(year_int, month_int, day_int, 0, 0, 0).unwrap()
+    let (date, time) = match input.split_once('T') {
+        Some((date, time)) => (date, Some(time)),
+        None => (input.as_str(), None),
+    };
+
+    let parts: Vec<&str> = date.split('-').collect();
+    assert!(
+        parts.len() == 3 && parts[0].len() == 4 && parts[1].len() == 2 && parts[2].len() == 2,
+        "Invalid created string {}, expected format YYYY-MM-DD or YYYY-MM-DDTHH:MM.",
+        input
+    );
+
+    let year = parts[0]
+        .parse::<u32>()
+        .unwrap_or_else(|_| panic!("Invalid year in created string {}.", input));
+    let month = parts[1]
+        .parse::<u8>()
+        .unwrap_or_else(|_| panic!("Invalid month in created string {}.", input));
+    let day = parts[2]
+        .parse::<u8>()
+        .unwrap_or_else(|_| panic!("Invalid day in created string {}.", input));
+
+    let (hour, minute) = match time {
+        Some(time) => {
+            let parts: Vec<&str> = time.split(':').collect();
+            assert!(
+                parts.len() == 2 && parts[0].len() == 2 && parts[1].len() == 2,
+                "Invalid created string {}, expected format YYYY-MM-DD or YYYY-MM-DDTHH:MM.",
+                input
+            );
+
+            let hour = parts[0]
+                .parse::<u8>()
+                .unwrap_or_else(|_| panic!("Invalid hour in created string {}.", input));
+            let minute = parts[1]
+                .parse::<u8>()
+                .unwrap_or_else(|_| panic!("Invalid minute in created string {}.", input));
+            (hour, minute)
+        }
+        None => (0, 0),
+    };
+
+    UtcDateTime::new(year, month, day, hour, minute, 0)
+        .unwrap_or_else(|_| panic!("Invalid date in created string {}.", input))
 }
@@ -1,11 +1,31 @@
 #[path = "./common.rs"]
 mod common;
-use common::{execute_manifest, mint_creator_badge, new_account, new_runner};
-
-use backeum_blueprint::data::{Membership, Trophy};
+use common::{
+    execute_manifest, mint_creator_badge, new_account, new_collection_component, new_runner,
+    new_runner_with_platform_fee, TestRunner,
+};
+
+use backeum_blueprint::data::{
+    CollectionInfo, CreatedGranularity, Creator, FeeMode, Membership, Trophy,
+};
+use backeum_blueprint::events::DonationEvent;
+use backeum_blueprint::util::COLLECTION_VERSION;
+use radix_engine::transaction::CommitResult;
 use scrypto::prelude::*;
 use transaction::builder::ManifestBuilder;
 
+// donation_event returns the donation event emitted by the given committed transaction.
+#[cfg(test)]
+fn donation_event(base: &TestRunner, commit: &CommitResult) -> DonationEvent {
+    let (_, data) = commit
+        .application_events
+        .iter()
+        .find(|(event_type, _)| base.test_runner.event_name(event_type) == "DonationEvent")
+        .expect("No donation event emitted.");
+
+    scrypto_decode(data).unwrap()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -39,6 +59,9 @@ mod tests {
                         lookup.proof("creator_badge_proof"),
                         "Trophy name",
                         "Kansulers trophy",
+                        vec![XRD],
+                        None::<String>,
+                        None::<Decimal>,
                     )
                 },
             );
@@ -62,7 +85,13 @@ mod tests {
             .withdraw_from_account(donation_account.wallet_address, XRD, dec!(150))
             .take_from_worktop(XRD, dec!(150), "donation_amount")
             .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
-                (lookup.bucket("donation_amount"),)
+                (
+                    lookup.bucket("donation_amount"),
+                    Some(donation_account.wallet_address),
+                    Some("Keep up the good work!".to_owned()),
+                    false,
+                    Some("newsletter".to_owned()),
+                )
             })
             .assert_worktop_contains(base.trophy_resource_address, dec!(1))
             .assert_worktop_contains(base.membership_resource_address, dec!(1))
@@ -79,7 +108,8 @@ mod tests {
             true,
         );
 
-        receipt.expect_commit_success();
+        let (_, _, _, minted_id): (Bucket, Bucket, Bucket, NonFungibleLocalId) =
+            receipt.expect_commit_success().output(2);
 
         assert_eq!(
             base.test_runner.get_component_balance(
@@ -110,6 +140,8 @@ mod tests {
             trophy_id = trophies.next().unwrap().clone();
         }
 
+        assert_eq!(minted_id, trophy_id);
+
         let trophy_data: Trophy = base
             .test_runner
             .get_non_fungible_data(base.trophy_resource_address, trophy_id.clone());
@@ -119,6 +151,16 @@ mod tests {
             .unwrap();
 
         assert_eq!(trophy_data.collection_id, result);
+        assert_eq!(trophy_data.currency, XRD);
+        assert_eq!(
+            trophy_data.original_donor,
+            Some(donation_account.wallet_address)
+        );
+        assert_eq!(
+            trophy_data.message,
+            Some("Keep up the good work!".to_owned())
+        );
+        assert_eq!(trophy_data.referrer, Some("newsletter".to_owned()));
 
         assert_eq!(trophy_data.name, "Trophy name");
         assert_eq!(trophy_data.description, "Kansulers trophy");
@@ -129,20 +171,24 @@ mod tests {
             trophy_data.info_url,
             UncheckedUrl::of("https://localhost:8080/p/kansuler".to_owned())
         );
-        assert_eq!(trophy_data.created, "2023-11-04");
+        assert_eq!(trophy_data.created, "2023-11-04T10:19");
         assert_eq!(trophy_data.donated, dec!(150));
+        assert_eq!(trophy_data.tier, "silver");
 
         assert_eq!(trophy_data.transactions.len(), 1);
         trophy_data.transactions.iter().for_each(|transaction| {
             assert_eq!(transaction.amount, dec!(150));
-            assert_eq!(transaction.created, "2023-11-04");
+            assert_eq!(transaction.created, "2023-11-04T10:19");
         });
 
         assert_eq!(
             trophy_data.key_image_url,
             UncheckedUrl::of(format!(
-                "https://localhost:8080/nft/collection/{}?donated=150&created=2023-11-04",
-                trophy_data.collection_id
+                "https://localhost:8080/nft/collection/{}?donated=150&created=2023-11-04T10:19&currency={}&tier=silver",
+                trophy_data.collection_id,
+                AddressBech32Encoder::new(&NetworkDefinition::simulator())
+                    .encode(&XRD.to_vec())
+                    .unwrap()
             ))
         );
 
@@ -171,19 +217,19 @@ mod tests {
             membership_data.info_url,
             UncheckedUrl::of("https://localhost:8080/p/kansuler".to_owned())
         );
-        assert_eq!(membership_data.created, "2023-11-04");
+        assert_eq!(membership_data.created, "2023-11-04T10:19");
         assert_eq!(membership_data.donated, dec!(150));
         assert_eq!(membership_data.creator, creator_badge_badge_id,);
         assert_eq!(membership_data.transactions.len(), 1);
         membership_data.transactions.iter().for_each(|transaction| {
             assert_eq!(transaction.amount, dec!(150));
-            assert_eq!(transaction.created, "2023-11-04");
+            assert_eq!(transaction.created, "2023-11-04T10:19");
         });
 
         assert_eq!(
             membership_data.key_image_url,
             UncheckedUrl::of(format!(
-                "https://localhost:8080/nft/membership/{}?donated=150&created=2023-11-04",
+                "https://localhost:8080/nft/membership/{}?donated=150&created=2023-11-04T10:19",
                 membership_data.creator_slug
             ))
         );
@@ -218,6 +264,9 @@ mod tests {
                         lookup.proof("creator_badge_proof"),
                         "Trophy name",
                         "Kansulers trophy",
+                        vec![XRD],
+                        None::<String>,
+                        None::<Decimal>,
                     )
                 },
             );
@@ -241,7 +290,13 @@ mod tests {
             .withdraw_from_account(donation_account.wallet_address, XRD, dec!(150))
             .take_from_worktop(XRD, dec!(150), "donation_amount")
             .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
-                (lookup.bucket("donation_amount"),)
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
             })
             .assert_worktop_contains(base.trophy_resource_address, dec!(1))
             .assert_worktop_contains(base.membership_resource_address, dec!(1))
@@ -296,6 +351,7 @@ mod tests {
                     (
                         lookup.bucket("donation_amount"),
                         lookup.proof("membership_proof"),
+                        None::<ComponentAddress>,
                     )
                 },
             )
@@ -371,19 +427,22 @@ mod tests {
             trophy_data.info_url,
             UncheckedUrl::of("https://localhost:8080/p/kansuler".to_owned())
         );
-        assert_eq!(trophy_data.created, "2023-11-04");
+        assert_eq!(trophy_data.created, "2023-11-04T10:19");
         assert_eq!(trophy_data.donated, dec!(150));
 
         assert_eq!(trophy_data.transactions.len(), 1);
         trophy_data.transactions.iter().for_each(|transaction| {
             assert_eq!(transaction.amount, dec!(150));
-            assert_eq!(transaction.created, "2023-11-04");
+            assert_eq!(transaction.created, "2023-11-04T10:19");
         });
         assert_eq!(
             trophy_data.key_image_url,
             UncheckedUrl::of(format!(
-                "https://localhost:8080/nft/collection/{}?donated=150&created=2023-11-04",
-                trophy_data.collection_id
+                "https://localhost:8080/nft/collection/{}?donated=150&created=2023-11-04T10:19&currency={}&tier=silver",
+                trophy_data.collection_id,
+                AddressBech32Encoder::new(&NetworkDefinition::simulator())
+                    .encode(&XRD.to_vec())
+                    .unwrap()
             ))
         );
 
@@ -412,19 +471,19 @@ mod tests {
             membership_data.info_url,
             UncheckedUrl::of("https://localhost:8080/p/kansuler".to_owned())
         );
-        assert_eq!(membership_data.created, "2023-11-04");
+        assert_eq!(membership_data.created, "2023-11-04T10:19");
         assert_eq!(membership_data.donated, dec!(300));
         assert_eq!(membership_data.creator, creator_badge_badge_id);
         assert_eq!(membership_data.transactions.len(), 2);
         membership_data.transactions.iter().for_each(|transaction| {
             assert_eq!(transaction.amount, dec!(150));
-            assert_eq!(transaction.created, "2023-11-04");
+            assert_eq!(transaction.created, "2023-11-04T10:19");
         });
 
         assert_eq!(
             membership_data.key_image_url,
             UncheckedUrl::of(format!(
-                "https://localhost:8080/nft/membership/{}?donated=300&created=2023-11-04",
+                "https://localhost:8080/nft/membership/{}?donated=300&created=2023-11-04T10:19",
                 membership_data.creator_slug
             ))
         );
@@ -459,6 +518,9 @@ mod tests {
                         lookup.proof("creator_badge_proof"),
                         "Trophy name",
                         "Kansulers trophy",
+                        vec![XRD],
+                        None::<String>,
+                        None::<Decimal>,
                     )
                 },
             );
@@ -502,7 +564,13 @@ mod tests {
             .withdraw_from_account(donation_account.wallet_address, XRD, dec!(150))
             .take_from_worktop(XRD, dec!(150), "donation_amount")
             .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
-                (lookup.bucket("donation_amount"),)
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
             })
             .assert_worktop_contains(base.trophy_resource_address, dec!(1))
             .assert_worktop_contains(base.thanks_token_resource_address, dec!(150))
@@ -534,6 +602,319 @@ mod tests {
         );
     }
 
+    #[test]
+    fn donate_mint_failure_below_minimum() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id: NonFungibleGlobalId;
+        {
+            creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+        }
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        // Create collection component
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Set the minimum donation
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                creator_badge_account.wallet_address,
+                creator_badge_badge_id.clone(),
+            )
+            .call_method(
+                collection_component,
+                "set_min_donation",
+                manifest_args!(dec!(100)),
+            );
+
+        // Execute it
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        // Attempt to donate below the minimum
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(50))
+            .take_from_worktop(XRD, dec!(50), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_failure();
+    }
+
+    #[test]
+    fn donation_goal_progress_success() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Donate and mint trophy
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(150))
+            .take_from_worktop(XRD, dec!(150), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        // Set the donation goal and read the progress
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                creator_badge_account.wallet_address,
+                creator_badge_badge_id.clone(),
+            )
+            .call_method(
+                collection_component,
+                "set_donation_goal",
+                manifest_args!(Some(dec!(1000))),
+            )
+            .call_method(collection_component, "get_progress", manifest_args!());
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account.public_key,
+            )],
+            true,
+        );
+
+        let (total_donated, donation_goal): (Decimal, Option<Decimal>) =
+            receipt.expect_commit_success().output(2);
+
+        assert_eq!(total_donated, dec!(150));
+        assert_eq!(donation_goal, Some(dec!(1000)));
+    }
+
+    #[test]
+    fn donate_mint_failure_paused() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Donate and pause the collection
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(creator_badge_account.wallet_address, XRD, dec!(10))
+            .take_from_worktop(XRD, dec!(10), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .create_proof_from_account_of_non_fungible(
+                creator_badge_account.wallet_address,
+                creator_badge_badge_id.clone(),
+            )
+            .call_method(collection_component, "set_paused", manifest_args!(true))
+            .deposit_batch(creator_badge_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        // Attempt to donate to the paused collection
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(150))
+            .take_from_worktop(XRD, dec!(150), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_failure();
+
+        // Withdrawals still work while paused
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                creator_badge_account.wallet_address,
+                creator_badge_badge_id,
+            )
+            .call_method(
+                collection_component,
+                "withdraw_donations",
+                manifest_args!(XRD),
+            )
+            .deposit_batch(creator_badge_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+    }
+
+    #[test]
+    fn get_collection_info_success() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Donate and mint trophy
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(150))
+            .take_from_worktop(XRD, dec!(150), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        // Read the collection info
+        let manifest = ManifestBuilder::new().call_method(
+            collection_component,
+            "get_collection_info",
+            manifest_args!(),
+        );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        let info: CollectionInfo = receipt.expect_commit_success().output(0);
+
+        let collection_id = AddressBech32Encoder::new(&NetworkDefinition::simulator())
+            .encode(&collection_component.to_vec())
+            .unwrap();
+
+        assert_eq!(info.creator_name, "Kansuler");
+        assert_eq!(info.creator_slug, "kansuler");
+        assert_eq!(info.creator, creator_badge_badge_id);
+        assert_eq!(info.collection_id, collection_id);
+        assert_eq!(info.fee_mode, FeeMode::Percentage(400));
+        assert_eq!(info.donations, vec![(XRD, dec!(144))]);
+    }
+
     #[test]
     fn donate_update_success() {
         let mut base = new_runner();
@@ -563,6 +944,9 @@ mod tests {
                         lookup.proof("creator_badge_proof"),
                         "Trophy name",
                         "Kansulers trophy",
+                        vec![XRD],
+                        None::<String>,
+                        None::<Decimal>,
                     )
                 },
             );
@@ -586,7 +970,13 @@ mod tests {
             .withdraw_from_account(donation_account.wallet_address, XRD, dec!(150))
             .take_from_worktop(XRD, dec!(150), "donation_amount")
             .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
-                (lookup.bucket("donation_amount"),)
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
             })
             .assert_worktop_contains(base.trophy_resource_address, dec!(1))
             .assert_worktop_contains(base.membership_resource_address, dec!(1))
@@ -685,14 +1075,17 @@ mod tests {
             trophy_data.info_url,
             UncheckedUrl::of("https://localhost:8080/p/kansuler".to_owned())
         );
-        assert_eq!(trophy_data.created, "2023-11-04");
+        assert_eq!(trophy_data.created, "2023-11-04T10:19");
         assert_eq!(trophy_data.donated, dec!(300));
 
         assert_eq!(
             trophy_data.key_image_url,
             UncheckedUrl::of(format!(
-                "https://localhost:8080/nft/collection/{}?donated=300&created=2023-11-04",
-                trophy_data.collection_id
+                "https://localhost:8080/nft/collection/{}?donated=300&created=2023-11-04T10:19&currency={}&tier=silver",
+                trophy_data.collection_id,
+                AddressBech32Encoder::new(&NetworkDefinition::simulator())
+                    .encode(&XRD.to_vec())
+                    .unwrap()
             ))
         );
     }
@@ -726,6 +1119,9 @@ mod tests {
                         lookup.proof("creator_badge_proof"),
                         "Trophy name",
                         "Kansulers trophy",
+                        vec![XRD],
+                        None::<String>,
+                        None::<Decimal>,
                     )
                 },
             );
@@ -749,7 +1145,13 @@ mod tests {
             .withdraw_from_account(donation_account.wallet_address, XRD, dec!(150))
             .take_from_worktop(XRD, dec!(150), "donation_amount")
             .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
-                (lookup.bucket("donation_amount"),)
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
             })
             .assert_worktop_contains(base.trophy_resource_address, dec!(1))
             .assert_worktop_contains(base.membership_resource_address, dec!(1))
@@ -867,14 +1269,17 @@ mod tests {
             trophy_data.info_url,
             UncheckedUrl::of("https://localhost:8080/p/kansuler".to_owned())
         );
-        assert_eq!(trophy_data.created, "2023-11-04");
+        assert_eq!(trophy_data.created, "2023-11-04T10:19");
         assert_eq!(trophy_data.donated, dec!(300));
 
         assert_eq!(
             trophy_data.key_image_url,
             UncheckedUrl::of(format!(
-                "https://localhost:8080/nft/collection/{}?donated=300&created=2023-11-04",
-                trophy_data.collection_id
+                "https://localhost:8080/nft/collection/{}?donated=300&created=2023-11-04T10:19&currency={}&tier=silver",
+                trophy_data.collection_id,
+                AddressBech32Encoder::new(&NetworkDefinition::simulator())
+                    .encode(&XRD.to_vec())
+                    .unwrap()
             ))
         );
 
@@ -887,14 +1292,14 @@ mod tests {
             membership_data.info_url,
             UncheckedUrl::of("https://localhost:8080/p/kansuler".to_owned())
         );
-        assert_eq!(membership_data.created, "2023-11-04");
+        assert_eq!(membership_data.created, "2023-11-04T10:19");
         assert_eq!(membership_data.donated, dec!(300));
         assert_eq!(membership_data.creator, creator_badge_badge_id);
 
         assert_eq!(
             membership_data.key_image_url,
             UncheckedUrl::of(format!(
-                "https://localhost:8080/nft/membership/{}?donated=300&created=2023-11-04",
+                "https://localhost:8080/nft/membership/{}?donated=300&created=2023-11-04T10:19",
                 membership_data.creator_slug
             ))
         );
@@ -928,6 +1333,9 @@ mod tests {
                         lookup.proof("creator_badge_proof"),
                         "Trophy name",
                         "Kansulers trophy",
+                        vec![XRD],
+                        None::<String>,
+                        None::<Decimal>,
                     )
                 },
             );
@@ -951,7 +1359,13 @@ mod tests {
             .withdraw_from_account(donation_account.wallet_address, XRD, dec!(150))
             .take_from_worktop(XRD, dec!(150), "donation_amount")
             .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
-                (lookup.bucket("donation_amount"),)
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
             })
             .assert_worktop_contains(base.trophy_resource_address, dec!(1))
             .assert_worktop_contains(base.thanks_token_resource_address, dec!(150))
@@ -1015,7 +1429,13 @@ mod tests {
             .withdraw_from_account(donation_account.wallet_address, XRD, dec!(100))
             .take_from_worktop(XRD, dec!(100), "donation_amount")
             .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
-                (lookup.bucket("donation_amount"),)
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
             })
             .assert_worktop_contains(base.trophy_resource_address, dec!(1))
             .assert_worktop_contains(base.thanks_token_resource_address, dec!(100))
@@ -1036,43 +1456,180 @@ mod tests {
     }
 
     #[test]
-    fn close_failure_auth() {
+    fn close_success_minter_badge_burned() {
         let mut base = new_runner();
 
         // Create an component admin account
         let creator_badge_account = new_account(&mut base.test_runner);
-        let creator_badge_badge_id: NonFungibleGlobalId;
-        {
-            creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
-        }
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
 
         // Create donation account
         let donation_account = new_account(&mut base.test_runner);
 
-        // Create collection components
-        let manifest = ManifestBuilder::new()
-            .create_proof_from_account_of_non_fungible(
-                creator_badge_account.wallet_address,
-                creator_badge_badge_id.clone(),
-            )
-            .pop_from_auth_zone("creator_badge_proof")
-            .call_method_with_name_lookup(
-                base.repository_component,
-                "new_collection_component",
-                |lookup| {
-                    (
-                        lookup.proof("creator_badge_proof"),
-                        "Trophy name",
-                        "Kansulers trophy",
-                    )
-                },
-            );
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
 
-        // Execute it
-        let receipt = execute_manifest(
-            &mut base.test_runner,
-            manifest,
-            "close_failure_auth_1",
+        // Donate and mint trophy
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(150))
+            .take_from_worktop(XRD, dec!(150), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        assert_eq!(
+            base.test_runner
+                .get_component_balance(collection_component, base.minter_badge_resource_address),
+            dec!(1)
+        );
+
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                creator_badge_account.wallet_address,
+                creator_badge_badge_id.clone(),
+            )
+            .call_method(collection_component, "close_collection", manifest_args!())
+            .deposit_batch(creator_badge_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        // The minter badge of the collection is burned
+        assert_eq!(
+            base.test_runner
+                .get_component_balance(collection_component, base.minter_badge_resource_address),
+            dec!(0)
+        );
+
+        // Trophies can no longer be minted
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(100))
+            .take_from_worktop(XRD, dec!(100), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_failure();
+
+        // Nor can existing trophies be updated
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(
+                donation_account.wallet_address,
+                base.trophy_resource_address,
+                dec!(1),
+            )
+            .take_all_from_worktop(base.trophy_resource_address, "trophy")
+            .create_proof_from_bucket_of_all("trophy", "trophy_proof")
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(100))
+            .take_from_worktop(XRD, dec!(100), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_update", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    lookup.proof("trophy_proof"),
+                )
+            })
+            .return_to_worktop("trophy")
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_failure();
+    }
+
+    #[test]
+    fn close_failure_auth() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id: NonFungibleGlobalId;
+        {
+            creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+        }
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        // Create collection components
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                creator_badge_account.wallet_address,
+                creator_badge_badge_id.clone(),
+            )
+            .pop_from_auth_zone("creator_badge_proof")
+            .call_method_with_name_lookup(
+                base.repository_component,
+                "new_collection_component",
+                |lookup| {
+                    (
+                        lookup.proof("creator_badge_proof"),
+                        "Trophy name",
+                        "Kansulers trophy",
+                        vec![XRD],
+                        None::<String>,
+                        None::<Decimal>,
+                    )
+                },
+            );
+
+        // Execute it
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "close_failure_auth_1",
             vec![NonFungibleGlobalId::from_public_key(
                 &creator_badge_account.public_key,
             )],
@@ -1129,6 +1686,9 @@ mod tests {
                         lookup.proof("creator_badge_proof"),
                         "Trophy name",
                         "Kansulers trophy",
+                        vec![XRD],
+                        None::<String>,
+                        None::<Decimal>,
                     )
                 },
             );
@@ -1216,6 +1776,9 @@ mod tests {
                         lookup.proof("creator_badge_proof"),
                         "Trophy name",
                         "Kansulers trophy",
+                        vec![XRD],
+                        None::<String>,
+                        None::<Decimal>,
                     )
                 },
             );
@@ -1239,7 +1802,13 @@ mod tests {
             .withdraw_from_account(donation_account.wallet_address, XRD, dec!(150))
             .take_from_worktop(XRD, dec!(150), "donation_amount")
             .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
-                (lookup.bucket("donation_amount"),)
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
             })
             .assert_worktop_contains(base.trophy_resource_address, dec!(1))
             .assert_worktop_contains(base.thanks_token_resource_address, dec!(150))
@@ -1317,6 +1886,9 @@ mod tests {
                         lookup.proof("creator_badge_proof"),
                         "Trophy name",
                         "Kansulers trophy",
+                        vec![XRD],
+                        None::<String>,
+                        None::<Decimal>,
                     )
                 },
             );
@@ -1338,11 +1910,26 @@ mod tests {
 
         // Attempt to withdraw with admin proof from the owner account
         let manifest = ManifestBuilder::new()
+            .withdraw_from_account(creator_badge_account_1.wallet_address, XRD, dec!(10))
+            .take_from_worktop(XRD, dec!(10), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
             .create_proof_from_account_of_non_fungible(
                 creator_badge_account_1.wallet_address,
                 creator_badge_badge_id_1,
             )
-            .call_method(collection_component, "withdraw_donations", manifest_args!())
+            .call_method(
+                collection_component,
+                "withdraw_donations",
+                manifest_args!(XRD),
+            )
             .deposit_batch(creator_badge_account_1.wallet_address);
 
         let receipt = execute_manifest(
@@ -1391,6 +1978,9 @@ mod tests {
                         lookup.proof("creator_badge_proof"),
                         "Trophy name",
                         "Kansulers trophy",
+                        vec![XRD],
+                        None::<String>,
+                        None::<Decimal>,
                     )
                 },
             );
@@ -1416,7 +2006,11 @@ mod tests {
                 creator_badge_account_2.wallet_address,
                 creator_badge_badge_id_2,
             )
-            .call_method(collection_component, "withdraw_donations", manifest_args!())
+            .call_method(
+                collection_component,
+                "withdraw_donations",
+                manifest_args!(XRD),
+            )
             .deposit_batch(creator_badge_account_2.wallet_address);
 
         let receipt = execute_manifest(
@@ -1431,4 +2025,5095 @@ mod tests {
 
         receipt.expect_commit_failure();
     }
+
+    #[test]
+    fn withdraw_amount_success() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Donate and mint trophy
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(150))
+            .take_from_worktop(XRD, dec!(150), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        // Withdraw part of the donations
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                creator_badge_account.wallet_address,
+                creator_badge_badge_id.clone(),
+            )
+            .call_method(
+                collection_component,
+                "withdraw_amount",
+                manifest_args!(XRD, dec!(100)),
+            )
+            .assert_worktop_contains(XRD, dec!(100))
+            .deposit_batch(creator_badge_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        assert_eq!(
+            base.test_runner
+                .get_component_balance(creator_badge_account.wallet_address, XRD),
+            dec!(10100)
+        );
+
+        // Attempt to withdraw more than what is left of the donations
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                creator_badge_account.wallet_address,
+                creator_badge_badge_id,
+            )
+            .call_method(
+                collection_component,
+                "withdraw_amount",
+                manifest_args!(XRD, dec!(100)),
+            )
+            .deposit_batch(creator_badge_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_failure();
+    }
+
+    #[test]
+    fn withdraw_donations_platform_fee_success() {
+        let mut base = new_runner_with_platform_fee(500);
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Donate and mint trophy
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(100))
+            .take_from_worktop(XRD, dec!(100), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        // Withdraw the donations, 5% of the withdrawal is kept as platform fee
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                creator_badge_account.wallet_address,
+                creator_badge_badge_id,
+            )
+            .call_method(
+                collection_component,
+                "withdraw_donations",
+                manifest_args!(XRD),
+            )
+            .assert_worktop_contains(XRD, dec!(91.2))
+            .deposit_batch(creator_badge_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        assert_eq!(
+            base.test_runner
+                .get_component_balance(creator_badge_account.wallet_address, XRD),
+            dec!(10091.2)
+        );
+
+        // The donation fee and the platform fee are both withdrawable by the repository owner
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                base.owner_account.wallet_address,
+                base.repository_owner_badge_global_id,
+            )
+            .call_method(collection_component, "withdraw_fees", manifest_args!(XRD))
+            .assert_worktop_contains(XRD, dec!(8.8))
+            .deposit_batch(base.owner_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &base.owner_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+    }
+
+    #[test]
+    fn donate_mint_batch_success() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Donate two donations in a single batch
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(150))
+            .take_from_worktop(XRD, dec!(100), "first_donation")
+            .take_from_worktop(XRD, dec!(50), "second_donation")
+            .call_method_with_name_lookup(collection_component, "donate_mint_batch", |lookup| {
+                (vec![
+                    lookup.bucket("first_donation"),
+                    lookup.bucket("second_donation"),
+                ],)
+            })
+            .assert_worktop_contains(base.trophy_resource_address, dec!(2))
+            .assert_worktop_contains(base.thanks_token_resource_address, dec!(150))
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        let trophy_vault = base.test_runner.get_component_vaults(
+            donation_account.wallet_address,
+            base.trophy_resource_address,
+        );
+
+        let trophy_ids: Vec<NonFungibleLocalId> = base
+            .test_runner
+            .inspect_non_fungible_vault(trophy_vault[0])
+            .unwrap()
+            .1
+            .collect();
+
+        let mut donated: Vec<Decimal> = vec![];
+        for trophy_id in trophy_ids {
+            let trophy_data: Trophy = base
+                .test_runner
+                .get_non_fungible_data(base.trophy_resource_address, trophy_id);
+            donated.push(trophy_data.donated);
+        }
+        donated.sort();
+
+        assert_eq!(donated, vec![dec!(50), dec!(100)]);
+    }
+
+    #[test]
+    fn donate_mint_no_fee_success() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Donate without fees on behalf of a platform user
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                base.owner_account.wallet_address,
+                base.repository_owner_badge_global_id.clone(),
+            )
+            .withdraw_from_account(base.owner_account.wallet_address, XRD, dec!(100))
+            .take_from_worktop(XRD, dec!(100), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint_no_fee", |lookup| {
+                (lookup.bucket("donation_amount"), None::<ComponentAddress>)
+            })
+            .assert_worktop_contains(base.trophy_resource_address, dec!(1))
+            .assert_worktop_contains(base.thanks_token_resource_address, dec!(100))
+            .deposit_batch(base.owner_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &base.owner_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        // The full donation is available to the creator
+        let manifest = ManifestBuilder::new().call_method(
+            collection_component,
+            "get_collection_info",
+            manifest_args!(),
+        );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &base.owner_account.public_key,
+            )],
+            true,
+        );
+
+        let info: CollectionInfo = receipt.expect_commit_success().output(0);
+        assert_eq!(info.donations, vec![(XRD, dec!(100))]);
+    }
+
+    #[test]
+    fn donate_mint_no_fee_failure_auth() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Attempt to donate without fees, without the repository owner badge
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(100))
+            .take_from_worktop(XRD, dec!(100), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint_no_fee", |lookup| {
+                (lookup.bucket("donation_amount"), None::<ComponentAddress>)
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_failure();
+    }
+
+    #[test]
+    fn donated_equals_claimable_success() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Donate and mint trophy
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(150))
+            .take_from_worktop(XRD, dec!(150), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        let trophy_vault = base.test_runner.get_component_vaults(
+            donation_account.wallet_address,
+            base.trophy_resource_address,
+        );
+
+        let trophy_id = base
+            .test_runner
+            .inspect_non_fungible_vault(trophy_vault[0])
+            .unwrap()
+            .1
+            .next()
+            .unwrap();
+
+        let trophy_data: Trophy = base
+            .test_runner
+            .get_non_fungible_data(base.trophy_resource_address, trophy_id);
+
+        // Withdraw the donations and the fees
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                creator_badge_account.wallet_address,
+                creator_badge_badge_id,
+            )
+            .create_proof_from_account_of_non_fungible(
+                base.owner_account.wallet_address,
+                base.repository_owner_badge_global_id.clone(),
+            )
+            .call_method(
+                collection_component,
+                "withdraw_donations",
+                manifest_args!(XRD),
+            )
+            .call_method(collection_component, "withdraw_fees", manifest_args!(XRD))
+            .deposit_batch(base.owner_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![
+                NonFungibleGlobalId::from_public_key(&creator_badge_account.public_key),
+                NonFungibleGlobalId::from_public_key(&base.owner_account.public_key),
+            ],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        // The trophy shows the full donation, which is what was withdrawn in total
+        assert_eq!(trophy_data.donated, dec!(150));
+        assert_eq!(
+            base.test_runner
+                .get_component_balance(base.owner_account.wallet_address, XRD),
+            dec!(10000) + trophy_data.donated
+        );
+    }
+
+    #[test]
+    fn preview_image_url_success() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        let manifest = ManifestBuilder::new().call_method(
+            collection_component,
+            "preview_image_url",
+            manifest_args!(dec!(150), "2023-11-04T10:19", XRD),
+        );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account.public_key,
+            )],
+            true,
+        );
+
+        let url: String = receipt.expect_commit_success().output(0);
+
+        let encoder = AddressBech32Encoder::new(&NetworkDefinition::simulator());
+        assert_eq!(
+            url,
+            format!(
+                "https://localhost:8080/nft/collection/{}?donated=150&created=2023-11-04T10:19&currency={}&tier=silver",
+                encoder.encode(&collection_component.to_vec()).unwrap(),
+                encoder.encode(&XRD.to_vec()).unwrap()
+            )
+        );
+    }
+
+    #[test]
+    fn set_fee_mode_success() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Set the fee to 10%
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                base.owner_account.wallet_address,
+                base.repository_owner_badge_global_id.clone(),
+            )
+            .call_method(
+                collection_component,
+                "set_fee_mode",
+                manifest_args!(FeeMode::Percentage(1000)),
+            );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &base.owner_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        // Donate and read the collection info
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(100))
+            .take_from_worktop(XRD, dec!(100), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .call_method(
+                collection_component,
+                "get_collection_info",
+                manifest_args!(),
+            )
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        let info: CollectionInfo = receipt.expect_commit_success().output(3);
+        assert_eq!(info.fee_mode, FeeMode::Percentage(1000));
+        assert_eq!(info.donations, vec![(XRD, dec!(90))]);
+    }
+
+    #[test]
+    fn set_fee_mode_failure_auth() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Attempt to set the fee with the creator badge
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                creator_badge_account.wallet_address,
+                creator_badge_badge_id,
+            )
+            .call_method(
+                collection_component,
+                "set_fee_mode",
+                manifest_args!(FeeMode::Percentage(0)),
+            );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_failure();
+    }
+
+    #[test]
+    fn get_donation_balance_success() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Donate and read the donation balance
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(150))
+            .take_from_worktop(XRD, dec!(150), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .call_method(
+                collection_component,
+                "get_donation_balance",
+                manifest_args!(),
+            )
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        let balance: Vec<(ResourceAddress, Decimal)> = receipt.expect_commit_success().output(3);
+        assert_eq!(balance, vec![(XRD, dec!(144))]);
+    }
+
+    #[test]
+    fn donate_update_failure_other_collection() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        // Create two collection components
+        let first_collection =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+        let second_collection =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Donate and mint trophy in the first collection
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(150))
+            .take_from_worktop(XRD, dec!(150), "donation_amount")
+            .call_method_with_name_lookup(first_collection, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        let trophy_vault = base.test_runner.get_component_vaults(
+            donation_account.wallet_address,
+            base.trophy_resource_address,
+        );
+
+        let trophy_id = base
+            .test_runner
+            .inspect_non_fungible_vault(trophy_vault[0])
+            .unwrap()
+            .1
+            .next()
+            .unwrap();
+
+        // Attempt to update the trophy through the second collection
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(150))
+            .take_from_worktop(XRD, dec!(150), "donation_amount")
+            .create_proof_from_account_of_non_fungible(
+                donation_account.wallet_address,
+                NonFungibleGlobalId::new(base.trophy_resource_address, trophy_id.clone()),
+            )
+            .create_proof_from_auth_zone_of_non_fungibles(
+                base.trophy_resource_address,
+                vec![trophy_id],
+                "proof",
+            )
+            .call_method_with_name_lookup(second_collection, "donate_update", |lookup| {
+                (lookup.bucket("donation_amount"), lookup.proof("proof"))
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_failure();
+    }
+
+    #[test]
+    fn withdraw_to_account_success() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Donate and mint trophy
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(150))
+            .take_from_worktop(XRD, dec!(150), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        // Withdraw the donations directly to the creator account
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                creator_badge_account.wallet_address,
+                creator_badge_badge_id,
+            )
+            .call_method(
+                collection_component,
+                "withdraw_to_account",
+                manifest_args!(creator_badge_account.wallet_address),
+            );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        assert_eq!(
+            base.test_runner
+                .get_component_balance(creator_badge_account.wallet_address, XRD),
+            dec!(10144)
+        );
+    }
+
+    #[test]
+    fn refund_donation_success() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Donate, mint trophy and refund it right away
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(150))
+            .take_from_worktop(XRD, dec!(150), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .take_all_from_worktop(base.trophy_resource_address, "trophy")
+            .take_all_from_worktop(base.thanks_token_resource_address, "thanks")
+            .call_method_with_name_lookup(collection_component, "refund_donation", |lookup| {
+                (lookup.bucket("trophy"), lookup.bucket("thanks"))
+            })
+            .assert_worktop_contains(XRD, dec!(150))
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        assert_eq!(
+            base.test_runner
+                .get_component_balance(donation_account.wallet_address, XRD),
+            dec!(10000)
+        );
+        assert_eq!(
+            base.test_runner.get_component_balance(
+                donation_account.wallet_address,
+                base.trophy_resource_address
+            ),
+            dec!(0)
+        );
+        assert_eq!(
+            base.test_runner.get_component_balance(
+                donation_account.wallet_address,
+                base.thanks_token_resource_address
+            ),
+            dec!(0)
+        );
+
+        // The refunded donation is taken off the funded total of the creator
+        let creator_data: Creator = base.test_runner.get_non_fungible_data(
+            base.creator_badge_resource_address,
+            creator_badge_badge_id.local_id().clone(),
+        );
+        assert_eq!(creator_data.funded, dec!(0));
+    }
+
+    #[test]
+    fn refund_donation_success_fees_taken() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Set a flat fee of 5 XRD
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                base.owner_account.wallet_address,
+                base.repository_owner_badge_global_id.clone(),
+            )
+            .call_method(
+                collection_component,
+                "set_fee_mode",
+                manifest_args!(FeeMode::Flat(dec!(5))),
+            );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &base.owner_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        // Donate twice to the same trophy, taking the flat fee from both donations
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(150))
+            .take_from_worktop(XRD, dec!(100), "donation_amount_1")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount_1"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .take_all_from_worktop(base.trophy_resource_address, "trophy")
+            .create_proof_from_bucket_of_all("trophy", "trophy_proof")
+            .take_from_worktop(XRD, dec!(50), "donation_amount_2")
+            .call_method_with_name_lookup(collection_component, "donate_update", |lookup| {
+                (
+                    lookup.bucket("donation_amount_2"),
+                    lookup.proof("trophy_proof"),
+                )
+            })
+            .return_to_worktop("trophy")
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        // Set a percentage fee, which does not apply to the donations already made
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                base.owner_account.wallet_address,
+                base.repository_owner_badge_global_id.clone(),
+            )
+            .call_method(
+                collection_component,
+                "set_fee_mode",
+                manifest_args!(FeeMode::Percentage(1000)),
+            );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &base.owner_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        // Refund the trophy, both flat fees are returned
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(
+                donation_account.wallet_address,
+                base.trophy_resource_address,
+                dec!(1),
+            )
+            .withdraw_from_account(
+                donation_account.wallet_address,
+                base.thanks_token_resource_address,
+                dec!(150),
+            )
+            .take_all_from_worktop(base.trophy_resource_address, "trophy")
+            .take_all_from_worktop(base.thanks_token_resource_address, "thanks")
+            .call_method_with_name_lookup(collection_component, "refund_donation", |lookup| {
+                (lookup.bucket("trophy"), lookup.bucket("thanks"))
+            })
+            .assert_worktop_contains(XRD, dec!(150))
+            .call_method(
+                collection_component,
+                "get_collection_info",
+                manifest_args!(),
+            )
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        let info: CollectionInfo = receipt.expect_commit_success().output(6);
+        assert_eq!(info.donations, vec![(XRD, dec!(0))]);
+        assert_eq!(
+            base.test_runner
+                .get_component_balance(donation_account.wallet_address, XRD),
+            dec!(10000)
+        );
+    }
+
+    #[test]
+    fn refund_donation_success_late_top_up() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Donate and mint trophy
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(100))
+            .take_from_worktop(XRD, dec!(100), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        base.test_runner
+            .advance_to_round_at_timestamp(Round::of(2), 1699093188267 + 11 * 60 * 1000); // 11 minutes later
+
+        // Top up the trophy after the refund grace period
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(
+                donation_account.wallet_address,
+                base.trophy_resource_address,
+                dec!(1),
+            )
+            .take_all_from_worktop(base.trophy_resource_address, "trophy")
+            .create_proof_from_bucket_of_all("trophy", "trophy_proof")
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(50))
+            .take_from_worktop(XRD, dec!(50), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_update", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    lookup.proof("trophy_proof"),
+                )
+            })
+            .return_to_worktop("trophy")
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        // Extend the refund grace period, so the trophy can be refunded again
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                creator_badge_account.wallet_address,
+                creator_badge_badge_id.clone(),
+            )
+            .call_method(
+                collection_component,
+                "set_refund_grace_period",
+                manifest_args!(60i64),
+            );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        // Only the donation made within the grace period is refunded
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(
+                donation_account.wallet_address,
+                base.trophy_resource_address,
+                dec!(1),
+            )
+            .withdraw_from_account(
+                donation_account.wallet_address,
+                base.thanks_token_resource_address,
+                dec!(100),
+            )
+            .take_all_from_worktop(base.trophy_resource_address, "trophy")
+            .take_all_from_worktop(base.thanks_token_resource_address, "thanks")
+            .call_method_with_name_lookup(collection_component, "refund_donation", |lookup| {
+                (lookup.bucket("trophy"), lookup.bucket("thanks"))
+            })
+            .assert_worktop_contains(XRD, dec!(100))
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        assert_eq!(
+            base.test_runner
+                .get_component_balance(donation_account.wallet_address, XRD),
+            dec!(9950)
+        );
+        assert_eq!(
+            base.test_runner.get_component_balance(
+                donation_account.wallet_address,
+                base.thanks_token_resource_address
+            ),
+            dec!(50)
+        );
+
+        // The top up stays funded to the creator
+        let creator_data: Creator = base.test_runner.get_non_fungible_data(
+            base.creator_badge_resource_address,
+            creator_badge_badge_id.local_id().clone(),
+        );
+        assert_eq!(creator_data.funded, dec!(50));
+    }
+
+    #[test]
+    fn refund_donation_failure_fees_withdrawn() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Donate and mint trophy
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(100))
+            .take_from_worktop(XRD, dec!(100), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        // Withdraw the fees of the donation
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                base.owner_account.wallet_address,
+                base.repository_owner_badge_global_id.clone(),
+            )
+            .call_method(collection_component, "withdraw_fees", manifest_args!(XRD))
+            .deposit_batch(base.owner_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &base.owner_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        // The fees can no longer be returned, so the refund fails
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(
+                donation_account.wallet_address,
+                base.trophy_resource_address,
+                dec!(1),
+            )
+            .withdraw_from_account(
+                donation_account.wallet_address,
+                base.thanks_token_resource_address,
+                dec!(100),
+            )
+            .take_all_from_worktop(base.trophy_resource_address, "trophy")
+            .take_all_from_worktop(base.thanks_token_resource_address, "thanks")
+            .call_method_with_name_lookup(collection_component, "refund_donation", |lookup| {
+                (lookup.bucket("trophy"), lookup.bucket("thanks"))
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_failure();
+    }
+
+    #[test]
+    fn refund_donation_success_day_granularity() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Only keep the day of the created date
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                creator_badge_account.wallet_address,
+                creator_badge_badge_id,
+            )
+            .call_method(
+                collection_component,
+                "set_created_granularity",
+                manifest_args!(CreatedGranularity::Day),
+            );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        // Donate and mint trophy, created on the day only
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(100))
+            .take_from_worktop(XRD, dec!(100), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        // Refund the trophy, the grace period starts from the mint time and not from midnight
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(
+                donation_account.wallet_address,
+                base.trophy_resource_address,
+                dec!(1),
+            )
+            .withdraw_from_account(
+                donation_account.wallet_address,
+                base.thanks_token_resource_address,
+                dec!(100),
+            )
+            .take_all_from_worktop(base.trophy_resource_address, "trophy")
+            .take_all_from_worktop(base.thanks_token_resource_address, "thanks")
+            .call_method_with_name_lookup(collection_component, "refund_donation", |lookup| {
+                (lookup.bucket("trophy"), lookup.bucket("thanks"))
+            })
+            .assert_worktop_contains(XRD, dec!(100))
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        // The grace period still ends after the given minutes
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(100))
+            .take_from_worktop(XRD, dec!(100), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        base.test_runner
+            .advance_to_round_at_timestamp(Round::of(2), 1699093188267 + 11 * 60 * 1000);
+
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(
+                donation_account.wallet_address,
+                base.trophy_resource_address,
+                dec!(1),
+            )
+            .withdraw_from_account(
+                donation_account.wallet_address,
+                base.thanks_token_resource_address,
+                dec!(100),
+            )
+            .take_all_from_worktop(base.trophy_resource_address, "trophy")
+            .take_all_from_worktop(base.thanks_token_resource_address, "thanks")
+            .call_method_with_name_lookup(collection_component, "refund_donation", |lookup| {
+                (lookup.bucket("trophy"), lookup.bucket("thanks"))
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_failure();
+    }
+
+    #[test]
+    fn refund_donation_failure_thanks_missing() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Donate, mint trophy and attempt to refund it while keeping part of the thanks tokens
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(150))
+            .take_from_worktop(XRD, dec!(150), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .take_all_from_worktop(base.trophy_resource_address, "trophy")
+            .take_from_worktop(base.thanks_token_resource_address, dec!(100), "thanks")
+            .call_method_with_name_lookup(collection_component, "refund_donation", |lookup| {
+                (lookup.bucket("trophy"), lookup.bucket("thanks"))
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_failure();
+    }
+
+    #[test]
+    fn refund_donation_failure_minter_missing() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Donate and mint a trophy while the collection still holds its minter badge
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(150))
+            .take_from_worktop(XRD, dec!(150), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        // Revoke the minter badge of the collection
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                base.owner_account.wallet_address,
+                base.repository_owner_badge_global_id.clone(),
+            )
+            .call_method(
+                base.repository_component,
+                "revoke_collection_minter",
+                manifest_args!(collection_component),
+            );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &base.owner_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        // Refunding needs the minter badge to burn the trophy, which is now missing
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(
+                donation_account.wallet_address,
+                base.trophy_resource_address,
+                dec!(1),
+            )
+            .withdraw_from_account(
+                donation_account.wallet_address,
+                base.thanks_token_resource_address,
+                dec!(150),
+            )
+            .take_all_from_worktop(base.trophy_resource_address, "trophy")
+            .take_all_from_worktop(base.thanks_token_resource_address, "thanks")
+            .call_method_with_name_lookup(collection_component, "refund_donation", |lookup| {
+                (lookup.bucket("trophy"), lookup.bucket("thanks"))
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_failure();
+    }
+
+    #[test]
+    fn refund_donation_failure_grace_period() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Donate and mint trophy
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(150))
+            .take_from_worktop(XRD, dec!(150), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        base.test_runner
+            .advance_to_round_at_timestamp(Round::of(2), 1699093188267 + 11 * 60 * 1000); // 11 minutes later
+
+        // Attempt to refund the trophy after the grace period
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(
+                donation_account.wallet_address,
+                base.trophy_resource_address,
+                dec!(1),
+            )
+            .withdraw_from_account(
+                donation_account.wallet_address,
+                base.thanks_token_resource_address,
+                dec!(150),
+            )
+            .take_all_from_worktop(base.trophy_resource_address, "trophy")
+            .take_all_from_worktop(base.thanks_token_resource_address, "thanks")
+            .call_method_with_name_lookup(collection_component, "refund_donation", |lookup| {
+                (lookup.bucket("trophy"), lookup.bucket("thanks"))
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_failure();
+    }
+
+    #[test]
+    fn get_supporter_count_success() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Donate and mint trophy, which counts as a supporter
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(100))
+            .take_from_worktop(XRD, dec!(100), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .call_method(
+                collection_component,
+                "get_supporter_count",
+                manifest_args!(),
+            )
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        let commit = receipt.expect_commit_success();
+        let supporter_count: u64 = commit.output(3);
+        assert_eq!(supporter_count, 1);
+
+        let event = donation_event(&base, commit);
+        assert_eq!(event.trophies_minted, 1);
+        assert_eq!(event.minted, true);
+
+        // Donate to the same trophy, which does not count as another supporter
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(
+                donation_account.wallet_address,
+                base.trophy_resource_address,
+                dec!(1),
+            )
+            .take_all_from_worktop(base.trophy_resource_address, "trophy")
+            .create_proof_from_bucket_of_all("trophy", "trophy_proof")
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(100))
+            .take_from_worktop(XRD, dec!(100), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_update", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    lookup.proof("trophy_proof"),
+                )
+            })
+            .return_to_worktop("trophy")
+            .call_method(
+                collection_component,
+                "get_supporter_count",
+                manifest_args!(),
+            )
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        let commit = receipt.expect_commit_success();
+        let supporter_count: u64 = commit.output(7);
+        assert_eq!(supporter_count, 1);
+
+        let event = donation_event(&base, commit);
+        assert_eq!(event.trophies_minted, 1);
+        assert_eq!(event.minted, false);
+
+        // Donate and mint another trophy
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(100))
+            .take_from_worktop(XRD, dec!(100), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .call_method(
+                collection_component,
+                "get_supporter_count",
+                manifest_args!(),
+            )
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        let commit = receipt.expect_commit_success();
+        let supporter_count: u64 = commit.output(3);
+        assert_eq!(supporter_count, 2);
+
+        let event = donation_event(&base, commit);
+        assert_eq!(event.trophies_minted, 2);
+        assert_eq!(event.minted, true);
+    }
+
+    #[test]
+    fn get_created_at_success() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Read the created date of the collection
+        let manifest = ManifestBuilder::new().call_method(
+            collection_component,
+            "get_created_at",
+            manifest_args!(),
+        );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account.public_key,
+            )],
+            true,
+        );
+
+        let created_at: String = receipt.expect_commit_success().output(0);
+        assert_eq!(created_at, "2023-11-04T10:19");
+    }
+
+    #[test]
+    fn rotate_owner_success() {
+        let mut base = new_runner();
+
+        // Create the current and the new component admin accounts
+        let creator_badge_account_1 = new_account(&mut base.test_runner);
+        let creator_badge_badge_id_1 = mint_creator_badge(&mut base, &creator_badge_account_1);
+        let creator_badge_account_2 = new_account(&mut base.test_runner);
+        let creator_badge_badge_id_2 = mint_creator_badge(&mut base, &creator_badge_account_2);
+
+        let collection_component = new_collection_component(
+            &mut base,
+            &creator_badge_account_1,
+            &creator_badge_badge_id_1,
+        );
+
+        // Rotate the owner to the new creator badge
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                creator_badge_account_1.wallet_address,
+                creator_badge_badge_id_1.clone(),
+            )
+            .call_method(
+                collection_component,
+                "rotate_owner",
+                manifest_args!(creator_badge_badge_id_2.clone()),
+            );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account_1.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        // Attempt to withdraw with the old creator badge
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                creator_badge_account_1.wallet_address,
+                creator_badge_badge_id_1,
+            )
+            .call_method(
+                collection_component,
+                "withdraw_donations",
+                manifest_args!(XRD),
+            )
+            .deposit_batch(creator_badge_account_1.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account_1.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_failure();
+
+        // Withdraw with the new creator badge
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(creator_badge_account_2.wallet_address, XRD, dec!(10))
+            .take_from_worktop(XRD, dec!(10), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .create_proof_from_account_of_non_fungible(
+                creator_badge_account_2.wallet_address,
+                creator_badge_badge_id_2.clone(),
+            )
+            .call_method(
+                collection_component,
+                "withdraw_donations",
+                manifest_args!(XRD),
+            )
+            .deposit_batch(creator_badge_account_2.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account_2.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        // The donation is funded to the new creator badge
+        let creator_data: Creator = base.test_runner.get_non_fungible_data(
+            base.creator_badge_resource_address,
+            creator_badge_badge_id_2.local_id().clone(),
+        );
+        assert_eq!(creator_data.funded, dec!(10));
+    }
+
+    #[test]
+    fn rotate_owner_success_team() {
+        let mut base = new_runner();
+
+        // Create the team and the new component admin accounts
+        let creator_badge_account_1 = new_account(&mut base.test_runner);
+        let creator_badge_account_2 = new_account(&mut base.test_runner);
+        let creator_badge_badge_id_2 = mint_creator_badge(&mut base, &creator_badge_account_2);
+
+        // Create a collection component with a creator badge and a team badge
+        let manifest = ManifestBuilder::new()
+            .call_method(
+                base.repository_component,
+                "new_collection_component_and_badge",
+                manifest_args!(
+                    "Kansuler",
+                    "kansuler",
+                    "Trophy name",
+                    "Trophy description",
+                    vec![XRD],
+                    None::<String>,
+                    None::<Decimal>,
+                    2u8,
+                ),
+            )
+            .deposit_batch(creator_badge_account_1.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account_1.public_key,
+            )],
+            true,
+        );
+
+        let collection_component = receipt.expect_commit_success().new_component_addresses()[0];
+
+        let manifest = ManifestBuilder::new().call_method(
+            collection_component,
+            "get_collection_info",
+            manifest_args!(),
+        );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account_1.public_key,
+            )],
+            true,
+        );
+
+        let info: CollectionInfo = receipt.expect_commit_success().output(0);
+        let creator_badge_badge_id_1 = info.creator;
+
+        let creator_badge_vault = base.test_runner.get_component_vaults(
+            creator_badge_account_1.wallet_address,
+            base.creator_badge_resource_address,
+        );
+        let team_badge_badge_id = base
+            .test_runner
+            .inspect_non_fungible_vault(creator_badge_vault[0])
+            .unwrap()
+            .1
+            .map(|local_id| NonFungibleGlobalId::new(base.creator_badge_resource_address, local_id))
+            .find(|global_id| *global_id != creator_badge_badge_id_1)
+            .unwrap();
+
+        // Rotate the owner to the new creator badge
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                creator_badge_account_1.wallet_address,
+                creator_badge_badge_id_1.clone(),
+            )
+            .call_method(
+                collection_component,
+                "rotate_owner",
+                manifest_args!(creator_badge_badge_id_2),
+            );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account_1.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        // Attempt to withdraw with the old creator badge
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                creator_badge_account_1.wallet_address,
+                creator_badge_badge_id_1,
+            )
+            .call_method(
+                collection_component,
+                "withdraw_donations",
+                manifest_args!(XRD),
+            )
+            .deposit_batch(creator_badge_account_1.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account_1.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_failure();
+
+        // Withdraw with the team badge, which is kept by the rotation
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(creator_badge_account_1.wallet_address, XRD, dec!(10))
+            .take_from_worktop(XRD, dec!(10), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .create_proof_from_account_of_non_fungible(
+                creator_badge_account_1.wallet_address,
+                team_badge_badge_id,
+            )
+            .call_method(
+                collection_component,
+                "withdraw_donations",
+                manifest_args!(XRD),
+            )
+            .deposit_batch(creator_badge_account_1.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account_1.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+    }
+
+    #[test]
+    fn donate_update_by_id_success() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        // Create the trophy holder and the boosting donation accounts
+        let donation_account = new_account(&mut base.test_runner);
+        let boost_account = new_account(&mut base.test_runner);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Donate and mint trophy
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(150))
+            .take_from_worktop(XRD, dec!(150), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        let trophy_vault = base.test_runner.get_component_vaults(
+            donation_account.wallet_address,
+            base.trophy_resource_address,
+        );
+
+        let trophy_id = base
+            .test_runner
+            .inspect_non_fungible_vault(trophy_vault[0])
+            .unwrap()
+            .1
+            .next()
+            .unwrap();
+
+        // Boost the trophy from another account without a proof of the trophy
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(boost_account.wallet_address, XRD, dec!(50))
+            .take_from_worktop(XRD, dec!(50), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_update_by_id", |lookup| {
+                (lookup.bucket("donation_amount"), trophy_id.clone())
+            })
+            .deposit_batch(boost_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &boost_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        let data: Trophy = base
+            .test_runner
+            .get_non_fungible_data(base.trophy_resource_address, trophy_id);
+        assert_eq!(data.donated, dec!(200));
+        assert_eq!(
+            base.test_runner.get_component_balance(
+                boost_account.wallet_address,
+                base.thanks_token_resource_address
+            ),
+            dec!(50)
+        );
+    }
+
+    #[test]
+    fn donate_update_by_id_failure_refund_after_boost() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        // Create the trophy holder and the boosting donation accounts
+        let donation_account = new_account(&mut base.test_runner);
+        let boost_account = new_account(&mut base.test_runner);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Donate and mint trophy
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(150))
+            .take_from_worktop(XRD, dec!(150), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        let trophy_vault = base.test_runner.get_component_vaults(
+            donation_account.wallet_address,
+            base.trophy_resource_address,
+        );
+
+        let trophy_id = base
+            .test_runner
+            .inspect_non_fungible_vault(trophy_vault[0])
+            .unwrap()
+            .1
+            .next()
+            .unwrap();
+
+        // Boost the trophy from another account, and hand the thanks tokens to the holder
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(boost_account.wallet_address, XRD, dec!(50))
+            .take_from_worktop(XRD, dec!(50), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_update_by_id", |lookup| {
+                (lookup.bucket("donation_amount"), trophy_id.clone())
+            })
+            .try_deposit_batch_or_abort(donation_account.wallet_address, None);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &boost_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        // The holder can not refund the boosted trophy, as it holds the donation of someone else
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(
+                donation_account.wallet_address,
+                base.trophy_resource_address,
+                dec!(1),
+            )
+            .withdraw_from_account(
+                donation_account.wallet_address,
+                base.thanks_token_resource_address,
+                dec!(200),
+            )
+            .take_all_from_worktop(base.trophy_resource_address, "trophy")
+            .take_all_from_worktop(base.thanks_token_resource_address, "thanks")
+            .call_method_with_name_lookup(collection_component, "refund_donation", |lookup| {
+                (lookup.bucket("trophy"), lookup.bucket("thanks"))
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_failure();
+
+        let data: Trophy = base
+            .test_runner
+            .get_non_fungible_data(base.trophy_resource_address, trophy_id);
+        assert!(!data.refundable);
+    }
+
+    #[test]
+    fn donate_update_by_id_failure_unknown_id() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Attempt to donate to a trophy that does not exist
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(50))
+            .take_from_worktop(XRD, dec!(50), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_update_by_id", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    NonFungibleLocalId::ruid([1u8; 32]),
+                )
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_failure();
+    }
+
+    #[test]
+    fn get_total_withdrawn_success() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Donate and mint trophy
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(150))
+            .take_from_worktop(XRD, dec!(150), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        // Withdraw part of the donations twice and read the total withdrawn
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                creator_badge_account.wallet_address,
+                creator_badge_badge_id,
+            )
+            .call_method(
+                collection_component,
+                "withdraw_amount",
+                manifest_args!(XRD, dec!(44)),
+            )
+            .call_method(
+                collection_component,
+                "withdraw_donations",
+                manifest_args!(XRD),
+            )
+            .call_method(
+                collection_component,
+                "get_total_withdrawn",
+                manifest_args!(),
+            )
+            .deposit_batch(creator_badge_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account.public_key,
+            )],
+            true,
+        );
+
+        let total_withdrawn: Decimal = receipt.expect_commit_success().output(3);
+        assert_eq!(total_withdrawn, dec!(144));
+    }
+
+    #[test]
+    fn mint_replacement_admin_badge_success() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        // Create the account the creator recovers to
+        let recovery_account = new_account(&mut base.test_runner);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Mint a replacement creator badge as the repository owner
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                base.owner_account.wallet_address,
+                base.repository_owner_badge_global_id.clone(),
+            )
+            .call_method(
+                collection_component,
+                "mint_replacement_admin_badge",
+                manifest_args!(),
+            )
+            .try_deposit_batch_or_abort(recovery_account.wallet_address, None);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &base.owner_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        let badge_vault = base.test_runner.get_component_vaults(
+            recovery_account.wallet_address,
+            base.creator_badge_resource_address,
+        );
+
+        let badge_id = base
+            .test_runner
+            .inspect_non_fungible_vault(badge_vault[0])
+            .unwrap()
+            .1
+            .next()
+            .unwrap();
+
+        // Attempt to withdraw with the lost creator badge
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                creator_badge_account.wallet_address,
+                creator_badge_badge_id,
+            )
+            .call_method(
+                collection_component,
+                "withdraw_donations",
+                manifest_args!(XRD),
+            )
+            .deposit_batch(creator_badge_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_failure();
+
+        // Withdraw with the replacement creator badge
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(recovery_account.wallet_address, XRD, dec!(10))
+            .take_from_worktop(XRD, dec!(10), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .create_proof_from_account_of_non_fungible(
+                recovery_account.wallet_address,
+                NonFungibleGlobalId::new(base.creator_badge_resource_address, badge_id.clone()),
+            )
+            .call_method(
+                collection_component,
+                "withdraw_donations",
+                manifest_args!(XRD),
+            )
+            .deposit_batch(recovery_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &recovery_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        // New trophies refer to the replacement creator badge, which is funded by the donation
+        let trophy_vault = base.test_runner.get_component_vaults(
+            recovery_account.wallet_address,
+            base.trophy_resource_address,
+        );
+
+        let trophy_id = base
+            .test_runner
+            .inspect_non_fungible_vault(trophy_vault[0])
+            .unwrap()
+            .1
+            .next()
+            .unwrap();
+
+        let trophy_data: Trophy = base
+            .test_runner
+            .get_non_fungible_data(base.trophy_resource_address, trophy_id);
+        assert_eq!(
+            trophy_data.creator,
+            NonFungibleGlobalId::new(base.creator_badge_resource_address, badge_id.clone())
+        );
+
+        let creator_data: Creator = base
+            .test_runner
+            .get_non_fungible_data(base.creator_badge_resource_address, badge_id);
+        assert_eq!(creator_data.funded, dec!(10));
+    }
+
+    #[test]
+    fn mint_replacement_admin_badge_failure_direct() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        // Attempt to mint a copy of the creator badge from the repository without a minter badge
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                base.owner_account.wallet_address,
+                base.repository_owner_badge_global_id.clone(),
+            )
+            .call_method(
+                base.repository_component,
+                "mint_replacement_creator_badge",
+                manifest_args!(creator_badge_badge_id.local_id().clone()),
+            )
+            .deposit_batch(base.owner_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &base.owner_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_failure();
+    }
+
+    #[test]
+    fn donate_mint_anonymous_success() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Donate and mint an anonymous trophy
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(150))
+            .take_from_worktop(XRD, dec!(150), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    true,
+                    None::<String>,
+                )
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        let trophy_vault = base.test_runner.get_component_vaults(
+            donation_account.wallet_address,
+            base.trophy_resource_address,
+        );
+
+        let trophy_id = base
+            .test_runner
+            .inspect_non_fungible_vault(trophy_vault[0])
+            .unwrap()
+            .1
+            .next()
+            .unwrap();
+
+        let trophy_data: Trophy = base
+            .test_runner
+            .get_non_fungible_data(base.trophy_resource_address, trophy_id);
+
+        assert_eq!(trophy_data.name, "Backeum Supporter");
+        assert!(trophy_data.is_anonymous);
+        assert_eq!(trophy_data.donated, dec!(150));
+    }
+
+    #[test]
+    fn get_fee_mode_success() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Read the fee mode of the collection
+        let manifest = ManifestBuilder::new().call_method(
+            collection_component,
+            "get_fee_mode",
+            manifest_args!(),
+        );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account.public_key,
+            )],
+            true,
+        );
+
+        let fee_mode: FeeMode = receipt.expect_commit_success().output(0);
+        assert_eq!(fee_mode, FeeMode::Percentage(400));
+    }
+
+    #[test]
+    fn custom_info_url_success() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        // Create a collection component with a custom info url
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                creator_badge_account.wallet_address,
+                creator_badge_badge_id.clone(),
+            )
+            .pop_from_auth_zone("creator_badge_proof")
+            .call_method_with_name_lookup(
+                base.repository_component,
+                "new_collection_component",
+                |lookup| {
+                    (
+                        lookup.proof("creator_badge_proof"),
+                        "Trophy name",
+                        "Kansulers trophy",
+                        vec![XRD],
+                        Some("https://kansuler.com/support".to_owned()),
+                        None::<Decimal>,
+                    )
+                },
+            );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account.public_key,
+            )],
+            true,
+        );
+
+        let collection_component = receipt.expect_commit_success().new_component_addresses()[0];
+
+        // Donate and mint trophy
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(150))
+            .take_from_worktop(XRD, dec!(150), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        let trophy_vault = base.test_runner.get_component_vaults(
+            donation_account.wallet_address,
+            base.trophy_resource_address,
+        );
+
+        let trophy_id = base
+            .test_runner
+            .inspect_non_fungible_vault(trophy_vault[0])
+            .unwrap()
+            .1
+            .next()
+            .unwrap();
+
+        let trophy_data: Trophy = base
+            .test_runner
+            .get_non_fungible_data(base.trophy_resource_address, trophy_id);
+
+        assert_eq!(
+            trophy_data.info_url,
+            UncheckedUrl::of("https://kansuler.com/support".to_owned())
+        );
+    }
+
+    #[test]
+    fn set_fee_mode_flat_success() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Set a flat fee of 5 XRD
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                base.owner_account.wallet_address,
+                base.repository_owner_badge_global_id.clone(),
+            )
+            .call_method(
+                collection_component,
+                "set_fee_mode",
+                manifest_args!(FeeMode::Flat(dec!(5))),
+            );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &base.owner_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        // Donate and read the collection info
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(100))
+            .take_from_worktop(XRD, dec!(100), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .call_method(
+                collection_component,
+                "get_collection_info",
+                manifest_args!(),
+            )
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        let info: CollectionInfo = receipt.expect_commit_success().output(3);
+        assert_eq!(info.fee_mode, FeeMode::Flat(dec!(5)));
+        assert_eq!(info.donations, vec![(XRD, dec!(95))]);
+    }
+
+    #[test]
+    fn refresh_image_urls_success() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Donate and mint trophy
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(150))
+            .take_from_worktop(XRD, dec!(150), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        let trophy_vault = base.test_runner.get_component_vaults(
+            donation_account.wallet_address,
+            base.trophy_resource_address,
+        );
+
+        let trophy_id = base
+            .test_runner
+            .inspect_non_fungible_vault(trophy_vault[0])
+            .unwrap()
+            .1
+            .next()
+            .unwrap();
+
+        // Change the base path without updating any trophies
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                base.owner_account.wallet_address,
+                base.repository_owner_badge_global_id.clone(),
+            )
+            .call_method(
+                base.repository_component,
+                "update_base_path",
+                manifest_args!("https://backeum.com", Vec::<NonFungibleLocalId>::new()),
+            );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &base.owner_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        // Refresh the image url of the trophy as the collection admin
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                creator_badge_account.wallet_address,
+                creator_badge_badge_id,
+            )
+            .call_method(
+                collection_component,
+                "refresh_image_urls",
+                manifest_args!(vec![trophy_id.clone()]),
+            );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        let trophy_data: Trophy = base
+            .test_runner
+            .get_non_fungible_data(base.trophy_resource_address, trophy_id);
+
+        assert_eq!(
+            trophy_data.key_image_url,
+            UncheckedUrl::of(format!(
+                "https://backeum.com/nft/collection/{}?donated=150&created=2023-11-04T10:19&currency={}&tier=silver",
+                trophy_data.collection_id,
+                AddressBech32Encoder::new(&NetworkDefinition::simulator())
+                    .encode(&XRD.to_vec())
+                    .unwrap()
+            ))
+        );
+    }
+
+    #[test]
+    fn donation_cap_failure_exceeded() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        // Create a collection component with a donation cap of 200
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                creator_badge_account.wallet_address,
+                creator_badge_badge_id.clone(),
+            )
+            .pop_from_auth_zone("creator_badge_proof")
+            .call_method_with_name_lookup(
+                base.repository_component,
+                "new_collection_component",
+                |lookup| {
+                    (
+                        lookup.proof("creator_badge_proof"),
+                        "Trophy name",
+                        "Kansulers trophy",
+                        vec![XRD],
+                        None::<String>,
+                        Some(dec!(200)),
+                    )
+                },
+            );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account.public_key,
+            )],
+            true,
+        );
+
+        let collection_component = receipt.expect_commit_success().new_component_addresses()[0];
+
+        // Donate up to the cap and read it
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(150))
+            .take_from_worktop(XRD, dec!(150), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .call_method(collection_component, "get_donation_cap", manifest_args!())
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        let donation_cap: Option<Decimal> = receipt.expect_commit_success().output(3);
+        assert_eq!(donation_cap, Some(dec!(200)));
+
+        // Attempt to donate over the cap
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(100))
+            .take_from_worktop(XRD, dec!(100), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_failure();
+    }
+
+    #[test]
+    fn donate_mint_divisibility_success() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        // Create donation account holding a token with a divisibility of 2
+        let donation_account = new_account(&mut base.test_runner);
+        let token = base.test_runner.create_fungible_resource(
+            dec!(1000),
+            2,
+            donation_account.wallet_address,
+        );
+
+        // Create a collection component accepting the token
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                creator_badge_account.wallet_address,
+                creator_badge_badge_id.clone(),
+            )
+            .pop_from_auth_zone("creator_badge_proof")
+            .call_method_with_name_lookup(
+                base.repository_component,
+                "new_collection_component",
+                |lookup| {
+                    (
+                        lookup.proof("creator_badge_proof"),
+                        "Trophy name",
+                        "Kansulers trophy",
+                        vec![token],
+                        None::<String>,
+                        None::<Decimal>,
+                    )
+                },
+            );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account.public_key,
+            )],
+            true,
+        );
+
+        let collection_component = receipt.expect_commit_success().new_component_addresses()[0];
+
+        // Donate an amount where the 4% fee is more precise than the divisibility
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, token, dec!("1.01"))
+            .take_from_worktop(token, dec!("1.01"), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .call_method(
+                collection_component,
+                "get_donation_balance",
+                manifest_args!(),
+            )
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        let balance: Vec<(ResourceAddress, Decimal)> = receipt.expect_commit_success().output(3);
+        assert_eq!(balance, vec![(token, dec!("0.97"))]);
+
+        let trophy_vault = base.test_runner.get_component_vaults(
+            donation_account.wallet_address,
+            base.trophy_resource_address,
+        );
+
+        let trophy_id = base
+            .test_runner
+            .inspect_non_fungible_vault(trophy_vault[0])
+            .unwrap()
+            .1
+            .next()
+            .unwrap();
+
+        let trophy_data: Trophy = base
+            .test_runner
+            .get_non_fungible_data(base.trophy_resource_address, trophy_id);
+
+        assert_eq!(trophy_data.donated, dec!("1.01"));
+        assert_eq!(trophy_data.currency, token);
+    }
+
+    #[test]
+    fn quote_donation_success() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Quote a donation of 150 XRD
+        let manifest = ManifestBuilder::new().call_method(
+            collection_component,
+            "quote_donation",
+            manifest_args!(XRD, dec!(150)),
+        );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account.public_key,
+            )],
+            true,
+        );
+
+        let quote: (Decimal, Decimal) = receipt.expect_commit_success().output(0);
+        assert_eq!(quote, (dec!(144), dec!(6)));
+    }
+
+    #[test]
+    fn get_version_success() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Read the version of the collection
+        let manifest = ManifestBuilder::new().call_method(
+            collection_component,
+            "get_version",
+            manifest_args!(),
+        );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account.public_key,
+            )],
+            true,
+        );
+
+        let version: u32 = receipt.expect_commit_success().output(0);
+        assert_eq!(version, COLLECTION_VERSION);
+    }
+
+    #[test]
+    fn donate_mint_failure_zero_amount() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Attempt to donate an empty bucket
+        let manifest = ManifestBuilder::new()
+            .take_all_from_worktop(XRD, "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_failure();
+    }
+
+    #[test]
+    fn donate_update_failure_zero_amount() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Donate and mint trophy
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(150))
+            .take_from_worktop(XRD, dec!(150), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        let trophy_vault = base.test_runner.get_component_vaults(
+            donation_account.wallet_address,
+            base.trophy_resource_address,
+        );
+
+        let trophy_id = base
+            .test_runner
+            .inspect_non_fungible_vault(trophy_vault[0])
+            .unwrap()
+            .1
+            .next()
+            .unwrap();
+
+        // Attempt to update the trophy with an empty bucket
+        let manifest = ManifestBuilder::new()
+            .take_all_from_worktop(XRD, "donation_amount")
+            .create_proof_from_account_of_non_fungible(
+                donation_account.wallet_address,
+                NonFungibleGlobalId::new(base.trophy_resource_address, trophy_id.clone()),
+            )
+            .create_proof_from_auth_zone_of_non_fungibles(
+                base.trophy_resource_address,
+                vec![trophy_id],
+                "proof",
+            )
+            .call_method_with_name_lookup(collection_component, "donate_update", |lookup| {
+                (lookup.bucket("donation_amount"), lookup.proof("proof"))
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_failure();
+    }
+
+    #[test]
+    fn get_user_metadata_success() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Read the creator metadata of the collection
+        let manifest = ManifestBuilder::new().call_method(
+            collection_component,
+            "get_user_metadata",
+            manifest_args!(),
+        );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account.public_key,
+            )],
+            true,
+        );
+
+        let (identity, name, slug): (String, String, String) =
+            receipt.expect_commit_success().output(0);
+        assert!(identity.ends_with(&format!(":{}", creator_badge_badge_id.local_id())));
+        assert_eq!(name, "Kansuler");
+        assert_eq!(slug, "kansuler");
+    }
+
+    #[test]
+    fn update_profile_success() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Update the profile of the collection
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                creator_badge_account.wallet_address,
+                creator_badge_badge_id,
+            )
+            .call_method(
+                collection_component,
+                "update_profile",
+                manifest_args!("Kansuler Studio", "kansuler-studio"),
+            );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        // New trophies are minted with the new profile
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(50))
+            .take_from_worktop(XRD, dec!(50), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        let trophy_vault = base.test_runner.get_component_vaults(
+            donation_account.wallet_address,
+            base.trophy_resource_address,
+        );
+
+        let trophy_id: NonFungibleLocalId = base
+            .test_runner
+            .inspect_non_fungible_vault(trophy_vault[0])
+            .unwrap()
+            .1
+            .next()
+            .unwrap();
+
+        let trophy_data: Trophy = base
+            .test_runner
+            .get_non_fungible_data(base.trophy_resource_address, trophy_id);
+
+        assert_eq!(trophy_data.creator_name, "Kansuler Studio");
+        assert_eq!(trophy_data.creator_slug, "kansuler-studio");
+        assert_eq!(
+            trophy_data.info_url,
+            UncheckedUrl::of("https://localhost:8080/p/kansuler-studio".to_owned())
+        );
+    }
+
+    #[test]
+    fn update_profile_failure_invalid_slug() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Update the profile with a slug that is not url safe
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                creator_badge_account.wallet_address,
+                creator_badge_badge_id,
+            )
+            .call_method(
+                collection_component,
+                "update_profile",
+                manifest_args!("Kansuler Studio", "kansuler studio"),
+            );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_failure();
+    }
+
+    #[test]
+    fn donate_update_to_total_success() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        // Donate and mint trophy
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(30))
+            .take_from_worktop(XRD, dec!(30), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        let trophy_vault = base.test_runner.get_component_vaults(
+            donation_account.wallet_address,
+            base.trophy_resource_address,
+        );
+
+        let trophy_id: NonFungibleLocalId = base
+            .test_runner
+            .inspect_non_fungible_vault(trophy_vault[0])
+            .unwrap()
+            .1
+            .next()
+            .unwrap();
+
+        // Top up the trophy to a total of 100, sending more than needed
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(100))
+            .take_from_worktop(XRD, dec!(100), "donation_amount")
+            .create_proof_from_account_of_non_fungible(
+                donation_account.wallet_address,
+                NonFungibleGlobalId::new(base.trophy_resource_address, trophy_id.clone()),
+            )
+            .create_proof_from_auth_zone_of_non_fungibles(
+                base.trophy_resource_address,
+                vec![trophy_id.clone()],
+                "proof",
+            )
+            .call_method_with_name_lookup(
+                collection_component,
+                "donate_update_to_total",
+                |lookup| {
+                    (
+                        lookup.bucket("donation_amount"),
+                        lookup.proof("proof"),
+                        dec!(100),
+                    )
+                },
+            )
+            .assert_worktop_contains(base.thanks_token_resource_address, dec!(70))
+            .assert_worktop_contains(XRD, dec!(30))
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        let trophy_data: Trophy = base
+            .test_runner
+            .get_non_fungible_data(base.trophy_resource_address, trophy_id);
+
+        assert_eq!(trophy_data.donated, dec!(100));
+        assert_eq!(
+            base.test_runner
+                .get_component_balance(donation_account.wallet_address, XRD),
+            dec!(9900)
+        );
+    }
+
+    #[test]
+    fn lock_fee_mode_success() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Lock the fee mode
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                base.owner_account.wallet_address,
+                base.repository_owner_badge_global_id.clone(),
+            )
+            .call_method(collection_component, "lock_fee_mode", manifest_args!())
+            .call_method(collection_component, "is_fee_mode_locked", manifest_args!());
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &base.owner_account.public_key,
+            )],
+            true,
+        );
+
+        let locked: bool = receipt.expect_commit_success().output(2);
+        assert!(locked);
+
+        // Changing the fee mode after the lock fails
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                base.owner_account.wallet_address,
+                base.repository_owner_badge_global_id.clone(),
+            )
+            .call_method(
+                collection_component,
+                "set_fee_mode",
+                manifest_args!(FeeMode::Percentage(1000)),
+            );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &base.owner_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_failure();
+    }
+
+    #[test]
+    fn lock_fee_mode_failure_auth() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Attempt to lock the fee mode with the creator badge
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                creator_badge_account.wallet_address,
+                creator_badge_badge_id,
+            )
+            .call_method(collection_component, "lock_fee_mode", manifest_args!());
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_failure();
+    }
+
+    #[test]
+    fn set_fee_mode_failure_negative() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Attempt to set a negative flat fee
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                base.owner_account.wallet_address,
+                base.repository_owner_badge_global_id.clone(),
+            )
+            .call_method(
+                collection_component,
+                "set_fee_mode",
+                manifest_args!(FeeMode::Flat(dec!(-5))),
+            );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &base.owner_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_failure();
+    }
+
+    #[test]
+    fn withdraw_donations_failure_empty() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Attempt to withdraw before any donation is made
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                creator_badge_account.wallet_address,
+                creator_badge_badge_id,
+            )
+            .call_method(
+                collection_component,
+                "withdraw_donations",
+                manifest_args!(XRD),
+            )
+            .deposit_batch(creator_badge_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_failure();
+    }
+
+    #[test]
+    fn donate_mint_bonus_success() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Donate with a 2x promotional bonus
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                base.owner_account.wallet_address,
+                base.repository_owner_badge_global_id.clone(),
+            )
+            .withdraw_from_account(base.owner_account.wallet_address, XRD, dec!(100))
+            .take_from_worktop(XRD, dec!(100), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint_bonus", |lookup| {
+                (lookup.bucket("donation_amount"), dec!(2))
+            })
+            .assert_worktop_contains(base.trophy_resource_address, dec!(1))
+            .assert_worktop_contains(base.thanks_token_resource_address, dec!(100))
+            .call_method(
+                collection_component,
+                "get_collection_info",
+                manifest_args!(),
+            )
+            .deposit_batch(base.owner_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &base.owner_account.public_key,
+            )],
+            true,
+        );
+
+        // Only the actual tokens are deposited
+        let info: CollectionInfo = receipt.expect_commit_success().output(6);
+        assert_eq!(info.donations, vec![(XRD, dec!(96))]);
+
+        let trophy_vault = base.test_runner.get_component_vaults(
+            base.owner_account.wallet_address,
+            base.trophy_resource_address,
+        );
+
+        let trophy_id = base
+            .test_runner
+            .inspect_non_fungible_vault(trophy_vault[0])
+            .unwrap()
+            .1
+            .next()
+            .unwrap();
+
+        let trophy_data: Trophy = base
+            .test_runner
+            .get_non_fungible_data(base.trophy_resource_address, trophy_id.clone());
+
+        assert_eq!(trophy_data.donated, dec!(200));
+
+        // Bonus trophies can not be refunded
+        let manifest = ManifestBuilder::new()
+            .withdraw_non_fungibles_from_account(
+                base.owner_account.wallet_address,
+                base.trophy_resource_address,
+                vec![trophy_id],
+            )
+            .withdraw_from_account(
+                base.owner_account.wallet_address,
+                base.thanks_token_resource_address,
+                dec!(100),
+            )
+            .take_all_from_worktop(base.trophy_resource_address, "trophy")
+            .take_all_from_worktop(base.thanks_token_resource_address, "thanks")
+            .call_method_with_name_lookup(collection_component, "refund_donation", |lookup| {
+                (lookup.bucket("trophy"), lookup.bucket("thanks"))
+            })
+            .deposit_batch(base.owner_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &base.owner_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_failure();
+    }
+
+    #[test]
+    fn donate_mint_bonus_failure_multiplier() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Attempt to donate with a multiplier below 1
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                base.owner_account.wallet_address,
+                base.repository_owner_badge_global_id.clone(),
+            )
+            .withdraw_from_account(base.owner_account.wallet_address, XRD, dec!(100))
+            .take_from_worktop(XRD, dec!(100), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint_bonus", |lookup| {
+                (lookup.bucket("donation_amount"), dec!("0.5"))
+            })
+            .deposit_batch(base.owner_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &base.owner_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_failure();
+    }
+
+    #[test]
+    fn refund_donation_failure_bonus_split() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Donate with a 2x promotional bonus, and split the bonus trophy into two trophies
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                base.owner_account.wallet_address,
+                base.repository_owner_badge_global_id.clone(),
+            )
+            .withdraw_from_account(base.owner_account.wallet_address, XRD, dec!(100))
+            .take_from_worktop(XRD, dec!(100), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint_bonus", |lookup| {
+                (lookup.bucket("donation_amount"), dec!(2))
+            })
+            .take_all_from_worktop(base.trophy_resource_address, "trophy")
+            .call_method_with_name_lookup(base.repository_component, "split_trophy", |lookup| {
+                (lookup.bucket("trophy"), vec![dec!(100), dec!(100)])
+            })
+            .deposit_batch(base.owner_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &base.owner_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        // The trophies split from the bonus trophy can not be refunded
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(
+                base.owner_account.wallet_address,
+                base.trophy_resource_address,
+                dec!(1),
+            )
+            .withdraw_from_account(
+                base.owner_account.wallet_address,
+                base.thanks_token_resource_address,
+                dec!(100),
+            )
+            .take_all_from_worktop(base.trophy_resource_address, "trophy")
+            .take_all_from_worktop(base.thanks_token_resource_address, "thanks")
+            .call_method_with_name_lookup(collection_component, "refund_donation", |lookup| {
+                (lookup.bucket("trophy"), lookup.bucket("thanks"))
+            })
+            .deposit_batch(base.owner_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &base.owner_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_failure();
+    }
+
+    #[test]
+    fn set_custom_metadata_success() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Set custom metadata and read it back
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                creator_badge_account.wallet_address,
+                creator_badge_badge_id,
+            )
+            .call_method(
+                collection_component,
+                "set_custom_metadata",
+                manifest_args!("twitter", "https://twitter.com/backeum"),
+            )
+            .call_method(
+                collection_component,
+                "get_custom_metadata",
+                manifest_args!("twitter"),
+            )
+            .call_method(
+                collection_component,
+                "get_custom_metadata",
+                manifest_args!("bio"),
+            );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account.public_key,
+            )],
+            true,
+        );
+
+        let commit = receipt.expect_commit_success();
+        let twitter: Option<String> = commit.output(2);
+        let bio: Option<String> = commit.output(3);
+        assert_eq!(twitter, Some("https://twitter.com/backeum".to_string()));
+        assert_eq!(bio, None);
+    }
+
+    #[test]
+    fn set_custom_metadata_failure_too_long() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Attempt to set a value over the length cap
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                creator_badge_account.wallet_address,
+                creator_badge_badge_id,
+            )
+            .call_method(
+                collection_component,
+                "set_custom_metadata",
+                manifest_args!("bio", "a".repeat(1025)),
+            );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_failure();
+    }
+
+    #[test]
+    fn get_accepted_resources_success() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Read the accepted resources of the collection
+        let manifest = ManifestBuilder::new().call_method(
+            collection_component,
+            "get_accepted_resources",
+            manifest_args!(),
+        );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account.public_key,
+            )],
+            true,
+        );
+
+        let accepted_resources: Vec<ResourceAddress> = receipt.expect_commit_success().output(0);
+        assert_eq!(accepted_resources, vec![XRD]);
+    }
+
+    #[test]
+    fn is_collection_trophy_success() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+        let other_collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        // Donate and mint trophy
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(100))
+            .take_from_worktop(XRD, dec!(100), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        let trophy_vault = base.test_runner.get_component_vaults(
+            donation_account.wallet_address,
+            base.trophy_resource_address,
+        );
+
+        let trophy_id = base
+            .test_runner
+            .inspect_non_fungible_vault(trophy_vault[0])
+            .unwrap()
+            .1
+            .next()
+            .unwrap();
+
+        // Check the trophy against both collections, and a proof of another resource
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                donation_account.wallet_address,
+                NonFungibleGlobalId::new(base.trophy_resource_address, trophy_id.clone()),
+            )
+            .create_proof_from_auth_zone_of_non_fungibles(
+                base.trophy_resource_address,
+                vec![trophy_id.clone()],
+                "proof_1",
+            )
+            .call_method_with_name_lookup(collection_component, "is_collection_trophy", |lookup| {
+                (lookup.proof("proof_1"),)
+            })
+            .create_proof_from_auth_zone_of_non_fungibles(
+                base.trophy_resource_address,
+                vec![trophy_id],
+                "proof_2",
+            )
+            .call_method_with_name_lookup(
+                other_collection_component,
+                "is_collection_trophy",
+                |lookup| (lookup.proof("proof_2"),),
+            )
+            .create_proof_from_account_of_amount(
+                donation_account.wallet_address,
+                base.thanks_token_resource_address,
+                dec!(1),
+            )
+            .create_proof_from_auth_zone_of_amount(
+                base.thanks_token_resource_address,
+                dec!(1),
+                "proof_3",
+            )
+            .call_method_with_name_lookup(collection_component, "is_collection_trophy", |lookup| {
+                (lookup.proof("proof_3"),)
+            });
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        let commit = receipt.expect_commit_success();
+        let own_collection: bool = commit.output(2);
+        let other_collection: bool = commit.output(4);
+        let other_resource: bool = commit.output(7);
+        assert!(own_collection);
+        assert!(!other_collection);
+        assert!(!other_resource);
+    }
+
+    #[test]
+    fn set_created_granularity_success() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Only keep the day of the created date
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                creator_badge_account.wallet_address,
+                creator_badge_badge_id,
+            )
+            .call_method(
+                collection_component,
+                "set_created_granularity",
+                manifest_args!(CreatedGranularity::Day),
+            )
+            .call_method(
+                collection_component,
+                "get_created_granularity",
+                manifest_args!(),
+            );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account.public_key,
+            )],
+            true,
+        );
+
+        let granularity: CreatedGranularity = receipt.expect_commit_success().output(2);
+        assert_eq!(granularity, CreatedGranularity::Day);
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        // Donate and mint trophy
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(100))
+            .take_from_worktop(XRD, dec!(100), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        let trophy_vault = base.test_runner.get_component_vaults(
+            donation_account.wallet_address,
+            base.trophy_resource_address,
+        );
+
+        let trophy_id = base
+            .test_runner
+            .inspect_non_fungible_vault(trophy_vault[0])
+            .unwrap()
+            .1
+            .next()
+            .unwrap();
+
+        let trophy_data: Trophy = base
+            .test_runner
+            .get_non_fungible_data(base.trophy_resource_address, trophy_id);
+
+        assert_eq!(trophy_data.created, "2023-11-04");
+        assert_eq!(trophy_data.transactions[0].created, "2023-11-04");
+    }
+
+    #[test]
+    fn withdraw_split_success() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        // Create donation and collaborator accounts
+        let donation_account = new_account(&mut base.test_runner);
+        let collaborator_account = new_account(&mut base.test_runner);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Donate and mint trophy
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(150))
+            .take_from_worktop(XRD, dec!(150), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        // Split the donations 70/30 between the creator and a collaborator
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                creator_badge_account.wallet_address,
+                creator_badge_badge_id,
+            )
+            .call_method(
+                collection_component,
+                "withdraw_split",
+                manifest_args!(vec![
+                    (creator_badge_account.wallet_address, 7000u16),
+                    (collaborator_account.wallet_address, 3000u16),
+                ]),
+            );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        assert_eq!(
+            base.test_runner
+                .get_component_balance(creator_badge_account.wallet_address, XRD),
+            dec!("10100.8")
+        );
+        assert_eq!(
+            base.test_runner
+                .get_component_balance(collaborator_account.wallet_address, XRD),
+            dec!("10043.2")
+        );
+    }
+
+    #[test]
+    fn withdraw_split_failure_invalid_shares() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        // Create collaborator account
+        let collaborator_account = new_account(&mut base.test_runner);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Attempt to split with shares that do not sum to 10000 basis points
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                creator_badge_account.wallet_address,
+                creator_badge_badge_id,
+            )
+            .call_method(
+                collection_component,
+                "withdraw_split",
+                manifest_args!(vec![
+                    (creator_badge_account.wallet_address, 7000u16),
+                    (collaborator_account.wallet_address, 2000u16),
+                ]),
+            );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_failure();
+    }
+
+    #[test]
+    fn fund_matching_success() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Fund a matching budget of 50 XRD
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                creator_badge_account.wallet_address,
+                creator_badge_badge_id,
+            )
+            .withdraw_from_account(creator_badge_account.wallet_address, XRD, dec!(50))
+            .take_from_worktop(XRD, dec!(50), "matching_amount")
+            .call_method_with_name_lookup(collection_component, "fund_matching", |lookup| {
+                (lookup.bucket("matching_amount"),)
+            });
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        // Donate more than the remaining budget
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(100))
+            .take_from_worktop(XRD, dec!(100), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .assert_worktop_contains(base.thanks_token_resource_address, dec!(100))
+            .call_method(
+                collection_component,
+                "get_matching_remaining",
+                manifest_args!(XRD),
+            )
+            .call_method(
+                collection_component,
+                "get_collection_info",
+                manifest_args!(),
+            )
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        let commit = receipt.expect_commit_success();
+        let matching_remaining: Decimal = commit.output(4);
+        let info: CollectionInfo = commit.output(5);
+        assert_eq!(matching_remaining, dec!(0));
+        assert_eq!(info.donations, vec![(XRD, dec!(144))]);
+
+        let trophy_vault = base.test_runner.get_component_vaults(
+            donation_account.wallet_address,
+            base.trophy_resource_address,
+        );
+
+        let trophy_id = base
+            .test_runner
+            .inspect_non_fungible_vault(trophy_vault[0])
+            .unwrap()
+            .1
+            .next()
+            .unwrap();
+
+        let trophy_data: Trophy = base
+            .test_runner
+            .get_non_fungible_data(base.trophy_resource_address, trophy_id);
+
+        // The trophy is credited with the donation and the matched amount
+        assert_eq!(trophy_data.donated, dec!(150));
+    }
+
+    #[test]
+    fn fund_matching_success_donate_update_by_id() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Donate and mint trophy before the matching budget is funded
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(100))
+            .take_from_worktop(XRD, dec!(100), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        let trophy_vault = base.test_runner.get_component_vaults(
+            donation_account.wallet_address,
+            base.trophy_resource_address,
+        );
+
+        let trophy_id = base
+            .test_runner
+            .inspect_non_fungible_vault(trophy_vault[0])
+            .unwrap()
+            .1
+            .next()
+            .unwrap();
+
+        // Fund a matching budget of 50 XRD
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                creator_badge_account.wallet_address,
+                creator_badge_badge_id.clone(),
+            )
+            .withdraw_from_account(creator_badge_account.wallet_address, XRD, dec!(50))
+            .take_from_worktop(XRD, dec!(50), "matching_amount")
+            .call_method_with_name_lookup(collection_component, "fund_matching", |lookup| {
+                (lookup.bucket("matching_amount"),)
+            });
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        // Boost the trophy by id from another account
+        let boost_account = new_account(&mut base.test_runner);
+
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(boost_account.wallet_address, XRD, dec!(50))
+            .take_from_worktop(XRD, dec!(50), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_update_by_id", |lookup| {
+                (lookup.bucket("donation_amount"), trophy_id.clone())
+            })
+            .assert_worktop_contains(base.thanks_token_resource_address, dec!(50))
+            .call_method(
+                collection_component,
+                "get_matching_remaining",
+                manifest_args!(XRD),
+            )
+            .deposit_batch(boost_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &boost_account.public_key,
+            )],
+            true,
+        );
+
+        let matching_remaining: Decimal = receipt.expect_commit_success().output(4);
+        assert_eq!(matching_remaining, dec!(0));
+
+        // The trophy is credited with the boost and the matched amount
+        let trophy_data: Trophy = base
+            .test_runner
+            .get_non_fungible_data(base.trophy_resource_address, trophy_id);
+        assert_eq!(trophy_data.donated, dec!(200));
+    }
+
+    #[test]
+    fn fund_matching_success_donate_update_to_total_unmatched() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Donate and mint trophy before the matching budget is funded
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(100))
+            .take_from_worktop(XRD, dec!(100), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        let trophy_vault = base.test_runner.get_component_vaults(
+            donation_account.wallet_address,
+            base.trophy_resource_address,
+        );
+
+        let trophy_id = base
+            .test_runner
+            .inspect_non_fungible_vault(trophy_vault[0])
+            .unwrap()
+            .1
+            .next()
+            .unwrap();
+
+        // Fund a matching budget of 50 XRD
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                creator_badge_account.wallet_address,
+                creator_badge_badge_id.clone(),
+            )
+            .withdraw_from_account(creator_badge_account.wallet_address, XRD, dec!(50))
+            .take_from_worktop(XRD, dec!(50), "matching_amount")
+            .call_method_with_name_lookup(collection_component, "fund_matching", |lookup| {
+                (lookup.bucket("matching_amount"),)
+            });
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        // Top up the trophy to a total of 130, which is not matched
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(50))
+            .take_from_worktop(XRD, dec!(50), "donation_amount")
+            .create_proof_from_account_of_non_fungible(
+                donation_account.wallet_address,
+                NonFungibleGlobalId::new(base.trophy_resource_address, trophy_id.clone()),
+            )
+            .create_proof_from_auth_zone_of_non_fungibles(
+                base.trophy_resource_address,
+                vec![trophy_id.clone()],
+                "proof",
+            )
+            .call_method_with_name_lookup(
+                collection_component,
+                "donate_update_to_total",
+                |lookup| {
+                    (
+                        lookup.bucket("donation_amount"),
+                        lookup.proof("proof"),
+                        dec!(130),
+                    )
+                },
+            )
+            .assert_worktop_contains(base.thanks_token_resource_address, dec!(30))
+            .assert_worktop_contains(XRD, dec!(20))
+            .call_method(
+                collection_component,
+                "get_matching_remaining",
+                manifest_args!(XRD),
+            )
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        let matching_remaining: Decimal = receipt.expect_commit_success().output(7);
+        assert_eq!(matching_remaining, dec!(50));
+
+        let trophy_data: Trophy = base
+            .test_runner
+            .get_non_fungible_data(base.trophy_resource_address, trophy_id);
+        assert_eq!(trophy_data.donated, dec!(130));
+    }
+
+    #[test]
+    fn withdraw_matching_success() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Fund a matching budget of 50 XRD
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                creator_badge_account.wallet_address,
+                creator_badge_badge_id.clone(),
+            )
+            .withdraw_from_account(creator_badge_account.wallet_address, XRD, dec!(50))
+            .take_from_worktop(XRD, dec!(50), "matching_amount")
+            .call_method_with_name_lookup(collection_component, "fund_matching", |lookup| {
+                (lookup.bucket("matching_amount"),)
+            });
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        // Take the unused budget back
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                creator_badge_account.wallet_address,
+                creator_badge_badge_id,
+            )
+            .call_method(
+                collection_component,
+                "withdraw_matching",
+                manifest_args!(XRD),
+            )
+            .assert_worktop_contains(XRD, dec!(50))
+            .call_method(
+                collection_component,
+                "get_matching_remaining",
+                manifest_args!(XRD),
+            )
+            .deposit_batch(creator_badge_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account.public_key,
+            )],
+            true,
+        );
+
+        let matching_remaining: Decimal = receipt.expect_commit_success().output(3);
+        assert_eq!(matching_remaining, dec!(0));
+        assert_eq!(
+            base.test_runner
+                .get_component_balance(creator_badge_account.wallet_address, XRD),
+            dec!(10000)
+        );
+    }
+
+    #[test]
+    fn withdraw_matching_failure_auth() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Attempt to take the budget without the creator badge
+        let manifest = ManifestBuilder::new()
+            .call_method(
+                collection_component,
+                "withdraw_matching",
+                manifest_args!(XRD),
+            )
+            .deposit_batch(creator_badge_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_failure();
+    }
+
+    #[test]
+    fn refund_donation_failure_matched_split() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Fund a matching budget of 100 XRD
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                creator_badge_account.wallet_address,
+                creator_badge_badge_id,
+            )
+            .withdraw_from_account(creator_badge_account.wallet_address, XRD, dec!(100))
+            .take_from_worktop(XRD, dec!(100), "matching_amount")
+            .call_method_with_name_lookup(collection_component, "fund_matching", |lookup| {
+                (lookup.bucket("matching_amount"),)
+            });
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        // Donate with a full match, and split the matched trophy into two trophies
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(100))
+            .take_from_worktop(XRD, dec!(100), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .take_all_from_worktop(base.trophy_resource_address, "trophy")
+            .call_method_with_name_lookup(base.repository_component, "split_trophy", |lookup| {
+                (lookup.bucket("trophy"), vec![dec!(100), dec!(100)])
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        // The trophies split from the matched trophy can not be refunded
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(
+                donation_account.wallet_address,
+                base.trophy_resource_address,
+                dec!(1),
+            )
+            .withdraw_from_account(
+                donation_account.wallet_address,
+                base.thanks_token_resource_address,
+                dec!(100),
+            )
+            .take_all_from_worktop(base.trophy_resource_address, "trophy")
+            .take_all_from_worktop(base.thanks_token_resource_address, "thanks")
+            .call_method_with_name_lookup(collection_component, "refund_donation", |lookup| {
+                (lookup.bucket("trophy"), lookup.bucket("thanks"))
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_failure();
+
+        assert_eq!(
+            base.test_runner
+                .get_component_balance(donation_account.wallet_address, XRD),
+            dec!(9900)
+        );
+    }
+
+    #[test]
+    fn donate_mint_backfill_success() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Backfill a historical donation
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                base.owner_account.wallet_address,
+                base.repository_owner_badge_global_id.clone(),
+            )
+            .withdraw_from_account(base.owner_account.wallet_address, XRD, dec!(100))
+            .take_from_worktop(XRD, dec!(100), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint_backfill", |lookup| {
+                (lookup.bucket("donation_amount"), "2023-11-01T12:00")
+            })
+            .assert_worktop_contains(base.trophy_resource_address, dec!(1))
+            .deposit_batch(base.owner_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &base.owner_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        let trophy_vault = base.test_runner.get_component_vaults(
+            base.owner_account.wallet_address,
+            base.trophy_resource_address,
+        );
+
+        let trophy_id = base
+            .test_runner
+            .inspect_non_fungible_vault(trophy_vault[0])
+            .unwrap()
+            .1
+            .next()
+            .unwrap();
+
+        let trophy_data: Trophy = base
+            .test_runner
+            .get_non_fungible_data(base.trophy_resource_address, trophy_id);
+
+        assert_eq!(trophy_data.created, "2023-11-01T12:00");
+        assert_eq!(trophy_data.transactions[0].created, "2023-11-01T12:00");
+        assert_eq!(trophy_data.donated, dec!(100));
+    }
+
+    #[test]
+    fn donate_mint_backfill_failure_future_date() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Attempt to backfill a donation dated in the future
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                base.owner_account.wallet_address,
+                base.repository_owner_badge_global_id.clone(),
+            )
+            .withdraw_from_account(base.owner_account.wallet_address, XRD, dec!(100))
+            .take_from_worktop(XRD, dec!(100), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint_backfill", |lookup| {
+                (lookup.bucket("donation_amount"), "2023-11-05T12:00")
+            })
+            .deposit_batch(base.owner_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &base.owner_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_failure();
+    }
 }
@@ -1,3 +1,4 @@
+use backeum_blueprint::util::{DEFAULT_TROPHY_RESOURCE_DESCRIPTION, DEFAULT_TROPHY_URL_TEMPLATE};
 use radix_engine::transaction::TransactionReceipt;
 use scrypto::prelude::*;
 use scrypto_unit::*;
@@ -64,12 +65,31 @@ pub fn new_account(test_runner: &mut DefaultTestRunner) -> Account {
 
 #[cfg(test)]
 pub fn mint_creator_badge(base: &mut TestRunner, account: &Account) -> NonFungibleGlobalId {
+    mint_creator_badge_named(base, account, "kansuler", "mint_creator_badge")
+}
+
+#[cfg(test)]
+pub fn mint_creator_badge_with_slug(
+    base: &mut TestRunner,
+    account: &Account,
+    creator_slug: &str,
+) -> NonFungibleGlobalId {
+    mint_creator_badge_named(base, account, creator_slug, "")
+}
+
+#[cfg(test)]
+fn mint_creator_badge_named(
+    base: &mut TestRunner,
+    account: &Account,
+    creator_slug: &str,
+    name: &str,
+) -> NonFungibleGlobalId {
     // Test the repository component via the new function.
     let manifest = ManifestBuilder::new()
         .call_method(
             base.repository_component,
             "mint_creator_badge",
-            manifest_args!("Kansuler", "kansuler"),
+            manifest_args!("Kansuler", creator_slug),
         )
         .assert_worktop_contains_any(base.creator_badge_resource_address)
         .deposit_batch(account.wallet_address);
@@ -78,7 +98,7 @@ pub fn mint_creator_badge(base: &mut TestRunner, account: &Account) -> NonFungib
     let receipt = execute_manifest(
         &mut base.test_runner,
         manifest,
-        "mint_creator_badge",
+        name,
         vec![NonFungibleGlobalId::from_public_key(&account.public_key)],
         true,
     );
@@ -105,6 +125,44 @@ pub fn mint_creator_badge(base: &mut TestRunner, account: &Account) -> NonFungib
     )
 }
 
+#[cfg(test)]
+pub fn new_collection_component(
+    base: &mut TestRunner,
+    account: &Account,
+    creator_badge_id: &NonFungibleGlobalId,
+) -> ComponentAddress {
+    // Create a collection component with the creator badge.
+    let manifest = ManifestBuilder::new()
+        .create_proof_from_account_of_non_fungible(account.wallet_address, creator_badge_id.clone())
+        .pop_from_auth_zone("creator_badge_proof")
+        .call_method_with_name_lookup(
+            base.repository_component,
+            "new_collection_component",
+            |lookup| {
+                (
+                    lookup.proof("creator_badge_proof"),
+                    "Trophy name",
+                    "Kansulers trophy",
+                    vec![XRD],
+                    None::<String>,
+                    None::<Decimal>,
+                )
+            },
+        );
+
+    // Execute the manifest.
+    let receipt = execute_manifest(
+        &mut base.test_runner,
+        manifest,
+        "",
+        vec![NonFungibleGlobalId::from_public_key(&account.public_key)],
+        true,
+    );
+
+    // Return the collection component address
+    receipt.expect_commit_success().new_component_addresses()[0]
+}
+
 #[cfg(test)]
 pub struct TestRunner {
     pub test_runner: DefaultTestRunner,
@@ -112,6 +170,7 @@ pub struct TestRunner {
     pub owner_account: Account,
     pub package_address: PackageAddress,
     pub package_owner_badge_global_id: NonFungibleGlobalId,
+    pub minter_badge_resource_address: ResourceAddress,
     pub creator_badge_resource_address: ResourceAddress,
     pub repository_owner_badge_global_id: NonFungibleGlobalId,
     pub membership_resource_address: ResourceAddress,
@@ -121,6 +180,11 @@ pub struct TestRunner {
 
 #[cfg(test)]
 pub fn new_runner() -> TestRunner {
+    new_runner_with_platform_fee(0)
+}
+
+#[cfg(test)]
+pub fn new_runner_with_platform_fee(platform_fee_bps: u16) -> TestRunner {
     let mut genesis = CustomGenesis::default(
         Epoch::of(1u64),
         CustomGenesis::default_consensus_manager_config(),
@@ -299,6 +363,9 @@ pub fn new_runner() -> TestRunner {
                 "https://localhost:8080",
                 repository_owner_badge_resource_address,
                 owner_account.wallet_address,
+                platform_fee_bps,
+                DEFAULT_TROPHY_URL_TEMPLATE,
+                DEFAULT_TROPHY_RESOURCE_DESCRIPTION,
             ),
         )
         .deposit_batch(owner_account.wallet_address);
@@ -319,6 +386,9 @@ pub fn new_runner() -> TestRunner {
     // Get the repository component address
     let repository_component = result.new_component_addresses()[0];
 
+    // Minter badge resource address
+    let minter_badge_resource_address = result.new_resource_addresses()[0];
+
     // Collection owner badge resource address
     let creator_badge_resource_address = result.new_resource_addresses()[1];
 
@@ -385,6 +455,7 @@ pub fn new_runner() -> TestRunner {
         owner_account,
         package_address,
         package_owner_badge_global_id,
+        minter_badge_resource_address,
         creator_badge_resource_address: creator_badge_resource_address,
         repository_owner_badge_global_id,
         membership_resource_address,
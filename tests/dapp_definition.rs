@@ -38,6 +38,9 @@ mod tests {
                         lookup.proof("creator_badge_proof"),
                         "Trophy name",
                         "Kansulers trophy",
+                        vec![XRD],
+                        None::<String>,
+                        None::<Decimal>,
                     )
                 },
             );
@@ -71,7 +74,13 @@ mod tests {
             .withdraw_from_account(donation_account.wallet_address, XRD, dec!(100))
             .take_from_worktop(XRD, dec!(100), "donation_amount")
             .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
-                (lookup.bucket("donation_amount"),)
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
             })
             .assert_worktop_contains(base.trophy_resource_address, dec!(1))
             .assert_worktop_contains(base.thanks_token_resource_address, dec!(100))
@@ -106,7 +115,7 @@ mod tests {
                 base.owner_account.wallet_address,
                 base.repository_owner_badge_global_id,
             )
-            .call_method(collection_component, "withdraw_fees", manifest_args!())
+            .call_method(collection_component, "withdraw_fees", manifest_args!(XRD))
             .assert_worktop_contains_any(XRD)
             .deposit_batch(base.owner_account.wallet_address);
 
@@ -155,6 +164,9 @@ mod tests {
                         lookup.proof("creator_badge_proof"),
                         "Trophy name",
                         "Kansulers trophy",
+                        vec![XRD],
+                        None::<String>,
+                        None::<Decimal>,
                     )
                 },
             );
@@ -1,8 +1,12 @@
 #[path = "./common.rs"]
 mod common;
-use common::{execute_manifest, mint_creator_badge, new_account, new_runner, Nft};
+use common::{
+    execute_manifest, mint_creator_badge, mint_creator_badge_with_slug, new_account,
+    new_collection_component, new_runner, Nft,
+};
 
-use backeum_blueprint::data::{Membership, Trophy};
+use backeum_blueprint::data::{CollectionInfo, CreatedGranularity, Membership, Trophy};
+use backeum_blueprint::util::MAX_MERGE_SIZE;
 use scrypto::prelude::*;
 use transaction::builder::ManifestBuilder;
 
@@ -36,6 +40,9 @@ mod tests {
                         lookup.proof("creator_badge_proof"),
                         "Trophy name",
                         "Kansulers trophy",
+                        vec![XRD],
+                        None::<String>,
+                        None::<Decimal>,
                     )
                 },
             );
@@ -195,6 +202,9 @@ mod tests {
                         lookup.proof("creator_badge_proof"),
                         "Trophy name",
                         "Kansulers trophy",
+                        vec![XRD],
+                        None::<String>,
+                        None::<Decimal>,
                     )
                 },
             );
@@ -225,7 +235,16 @@ mod tests {
             .call_method(
                 base.repository_component,
                 "new_collection_component_and_badge",
-                manifest_args!("Kansuler", "kansuler", "Trophy name", "Trophy description"),
+                manifest_args!(
+                    "Kansuler",
+                    "kansuler",
+                    "Trophy name",
+                    "Trophy description",
+                    vec![XRD],
+                    None::<String>,
+                    None::<Decimal>,
+                    1u8,
+                ),
             )
             .assert_worktop_contains(base.creator_badge_resource_address, dec!(1))
             .deposit_batch(creator_badge_account.wallet_address);
@@ -264,6 +283,17 @@ mod tests {
 
         // Create two collection components
         let manifest = ManifestBuilder::new()
+            .withdraw_from_account(creator_badge_account.wallet_address, XRD, dec!(10))
+            .take_from_worktop(XRD, dec!(10), "donation_amount")
+            .call_method_with_name_lookup(collection_component_address, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
             .create_proof_from_account_of_non_fungible(
                 creator_badge_account.wallet_address,
                 creator_badge_global_id,
@@ -271,7 +301,7 @@ mod tests {
             .call_method(
                 collection_component_address,
                 "withdraw_donations",
-                manifest_args!(),
+                manifest_args!(XRD),
             )
             .deposit_batch(creator_badge_account.wallet_address);
 
@@ -320,6 +350,9 @@ mod tests {
                         lookup.proof("creator_badge_proof"),
                         "Trophy name",
                         "Kansulers trophy",
+                        vec![XRD],
+                        None::<String>,
+                        None::<Decimal>,
                     )
                 },
             );
@@ -343,12 +376,24 @@ mod tests {
             .withdraw_from_account(donation_account.wallet_address, XRD, dec!(500))
             .take_from_worktop(XRD, dec!(250), "donation_amount_1")
             .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
-                (lookup.bucket("donation_amount_1"),)
+                (
+                    lookup.bucket("donation_amount_1"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
             })
             .assert_worktop_contains(base.trophy_resource_address, dec!(1))
             .take_from_worktop(XRD, dec!(250), "donation_amount_2")
             .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
-                (lookup.bucket("donation_amount_2"),)
+                (
+                    lookup.bucket("donation_amount_2"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
             })
             .assert_worktop_contains(base.trophy_resource_address, dec!(2))
             .take_all_from_worktop(base.trophy_resource_address, "trophies")
@@ -417,19 +462,22 @@ mod tests {
             trophy_data.info_url,
             UncheckedUrl::of("https://localhost:8080/p/kansuler".to_owned())
         );
-        assert_eq!(trophy_data.created, "2023-11-04");
+        assert_eq!(trophy_data.created, "2023-11-04T10:19");
         assert_eq!(trophy_data.donated, dec!(500));
         assert_eq!(trophy_data.transactions.len(), 2);
         trophy_data.transactions.iter().for_each(|transaction| {
             assert_eq!(transaction.amount, dec!(250));
-            assert_eq!(transaction.created, "2023-11-04");
+            assert_eq!(transaction.created, "2023-11-04T10:19");
         });
 
         assert_eq!(
             trophy_data.key_image_url,
             UncheckedUrl::of(format!(
-                "https://localhost:8080/nft/collection/{}?donated=500&created=2023-11-04",
-                trophy_data.collection_id
+                "https://localhost:8080/nft/collection/{}?donated=500&created=2023-11-04T10:19&currency={}&tier=silver",
+                trophy_data.collection_id,
+                AddressBech32Encoder::new(&NetworkDefinition::simulator())
+                    .encode(&XRD.to_vec())
+                    .unwrap()
             ))
         );
 
@@ -441,7 +489,13 @@ mod tests {
             .withdraw_from_account(donation_account.wallet_address, XRD, dec!(500))
             .take_from_worktop(XRD, dec!(500), "donation_amount_1")
             .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
-                (lookup.bucket("donation_amount_1"),)
+                (
+                    lookup.bucket("donation_amount_1"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
             })
             .withdraw_non_fungibles_from_account(
                 donation_account.wallet_address,
@@ -515,21 +569,24 @@ mod tests {
             trophy_data.info_url,
             UncheckedUrl::of("https://localhost:8080/p/kansuler".to_owned())
         );
-        assert_eq!(trophy_data.created, "2023-11-04");
+        assert_eq!(trophy_data.created, "2023-11-04T10:19");
         assert_eq!(trophy_data.donated, dec!(1000));
         assert_eq!(trophy_data.transactions.len(), 3);
         assert_eq!(trophy_data.transactions[0].amount, dec!(250));
-        assert_eq!(trophy_data.transactions[0].created, "2023-11-04");
+        assert_eq!(trophy_data.transactions[0].created, "2023-11-04T10:19");
         assert_eq!(trophy_data.transactions[1].amount, dec!(250));
-        assert_eq!(trophy_data.transactions[1].created, "2023-11-04");
+        assert_eq!(trophy_data.transactions[1].created, "2023-11-04T10:19");
         assert_eq!(trophy_data.transactions[2].amount, dec!(500));
-        assert_eq!(trophy_data.transactions[2].created, "2023-11-05");
+        assert_eq!(trophy_data.transactions[2].created, "2023-11-05T00:00");
 
         assert_eq!(
             trophy_data.key_image_url,
             UncheckedUrl::of(format!(
-                "https://localhost:8080/nft/collection/{}?donated=1000&created=2023-11-04",
-                trophy_data.collection_id
+                "https://localhost:8080/nft/collection/{}?donated=1000&created=2023-11-04T10:19&currency={}&tier=gold",
+                trophy_data.collection_id,
+                AddressBech32Encoder::new(&NetworkDefinition::simulator())
+                    .encode(&XRD.to_vec())
+                    .unwrap()
             ))
         );
     }
@@ -563,6 +620,9 @@ mod tests {
                         lookup.proof("creator_badge_proof"),
                         "Trophy name",
                         "Kansulers trophy",
+                        vec![XRD],
+                        None::<String>,
+                        None::<Decimal>,
                     )
                 },
             );
@@ -586,12 +646,24 @@ mod tests {
             .withdraw_from_account(donation_account.wallet_address, XRD, dec!(500))
             .take_from_worktop(XRD, dec!(250), "donation_amount_1")
             .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
-                (lookup.bucket("donation_amount_1"),)
+                (
+                    lookup.bucket("donation_amount_1"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
             })
             .assert_worktop_contains(base.membership_resource_address, dec!(1))
             .take_from_worktop(XRD, dec!(250), "donation_amount_2")
             .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
-                (lookup.bucket("donation_amount_2"),)
+                (
+                    lookup.bucket("donation_amount_2"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
             })
             .assert_worktop_contains(base.membership_resource_address, dec!(2))
             .take_all_from_worktop(base.membership_resource_address, "memberships")
@@ -656,18 +728,18 @@ mod tests {
             membership_data.info_url,
             UncheckedUrl::of("https://localhost:8080/p/kansuler".to_owned())
         );
-        assert_eq!(membership_data.created, "2023-11-04");
+        assert_eq!(membership_data.created, "2023-11-04T10:19");
         assert_eq!(membership_data.donated, dec!(500));
         assert_eq!(membership_data.transactions.len(), 2);
         membership_data.transactions.iter().for_each(|transaction| {
             assert_eq!(transaction.amount, dec!(250));
-            assert_eq!(transaction.created, "2023-11-04");
+            assert_eq!(transaction.created, "2023-11-04T10:19");
         });
 
         assert_eq!(
             membership_data.key_image_url,
             UncheckedUrl::of(format!(
-                "https://localhost:8080/nft/membership/{}?donated=500&created=2023-11-04",
+                "https://localhost:8080/nft/membership/{}?donated=500&created=2023-11-04T10:19",
                 membership_data.creator_slug
             ))
         );
@@ -680,7 +752,13 @@ mod tests {
             .withdraw_from_account(donation_account.wallet_address, XRD, dec!(500))
             .take_from_worktop(XRD, dec!(500), "donation_amount_1")
             .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
-                (lookup.bucket("donation_amount_1"),)
+                (
+                    lookup.bucket("donation_amount_1"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
             })
             .withdraw_non_fungibles_from_account(
                 donation_account.wallet_address,
@@ -750,20 +828,20 @@ mod tests {
             membership_data.info_url,
             UncheckedUrl::of("https://localhost:8080/p/kansuler".to_owned())
         );
-        assert_eq!(membership_data.created, "2023-11-04");
+        assert_eq!(membership_data.created, "2023-11-04T10:19");
         assert_eq!(membership_data.donated, dec!(1000));
         assert_eq!(membership_data.transactions.len(), 3);
         assert_eq!(membership_data.transactions[0].amount, dec!(250));
-        assert_eq!(membership_data.transactions[0].created, "2023-11-04");
+        assert_eq!(membership_data.transactions[0].created, "2023-11-04T10:19");
         assert_eq!(membership_data.transactions[1].amount, dec!(250));
-        assert_eq!(membership_data.transactions[1].created, "2023-11-04");
+        assert_eq!(membership_data.transactions[1].created, "2023-11-04T10:19");
         assert_eq!(membership_data.transactions[2].amount, dec!(500));
-        assert_eq!(membership_data.transactions[2].created, "2023-11-05");
+        assert_eq!(membership_data.transactions[2].created, "2023-11-05T00:00");
 
         assert_eq!(
             membership_data.key_image_url,
             UncheckedUrl::of(format!(
-                "https://localhost:8080/nft/membership/{}?donated=1000&created=2023-11-04",
+                "https://localhost:8080/nft/membership/{}?donated=1000&created=2023-11-04T10:19",
                 membership_data.creator_slug
             ))
         );
@@ -800,6 +878,9 @@ mod tests {
                         lookup.proof("creator_badge_proof_1"),
                         "Trophy name",
                         "Kansulers trophy",
+                        vec![XRD],
+                        None::<String>,
+                        None::<Decimal>,
                     )
                 },
             )
@@ -811,6 +892,9 @@ mod tests {
                         lookup.proof("creator_badge_proof_2"),
                         "Trophy name",
                         "Kansulers trophy",
+                        vec![XRD],
+                        None::<String>,
+                        None::<Decimal>,
                     )
                 },
             );
@@ -835,12 +919,24 @@ mod tests {
             .withdraw_from_account(donation_account.wallet_address, XRD, dec!(500))
             .take_from_worktop(XRD, dec!(250), "donation_amount_1")
             .call_method_with_name_lookup(collection_component_1, "donate_mint", |lookup| {
-                (lookup.bucket("donation_amount_1"),)
+                (
+                    lookup.bucket("donation_amount_1"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
             })
             .assert_worktop_contains(base.trophy_resource_address, dec!(1))
             .take_from_worktop(XRD, dec!(250), "donation_amount_2")
             .call_method_with_name_lookup(collection_component_2, "donate_mint", |lookup| {
-                (lookup.bucket("donation_amount_2"),)
+                (
+                    lookup.bucket("donation_amount_2"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
             })
             .assert_worktop_contains(base.trophy_resource_address, dec!(2))
             .take_all_from_worktop(base.trophy_resource_address, "trophies")
@@ -889,7 +985,8 @@ mod tests {
         }
         let creator_badge_2_badge_id: NonFungibleGlobalId;
         {
-            creator_badge_2_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+            creator_badge_2_badge_id =
+                mint_creator_badge_with_slug(&mut base, &creator_badge_account, "kansuler-2");
         }
 
         assert_ne!(
@@ -915,6 +1012,9 @@ mod tests {
                         lookup.proof("creator_badge_proof_1"),
                         "Trophy name",
                         "Kansulers trophy",
+                        vec![XRD],
+                        None::<String>,
+                        None::<Decimal>,
                     )
                 },
             )
@@ -931,6 +1031,9 @@ mod tests {
                         lookup.proof("creator_badge_proof_2"),
                         "Trophy name",
                         "Kansulers trophy",
+                        vec![XRD],
+                        None::<String>,
+                        None::<Decimal>,
                     )
                 },
             );
@@ -955,12 +1058,24 @@ mod tests {
             .withdraw_from_account(donation_account.wallet_address, XRD, dec!(500))
             .take_from_worktop(XRD, dec!(250), "donation_amount_1")
             .call_method_with_name_lookup(collection_component_1, "donate_mint", |lookup| {
-                (lookup.bucket("donation_amount_1"),)
+                (
+                    lookup.bucket("donation_amount_1"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
             })
             .assert_worktop_contains(base.membership_resource_address, dec!(1))
             .take_from_worktop(XRD, dec!(250), "donation_amount_2")
             .call_method_with_name_lookup(collection_component_2, "donate_mint", |lookup| {
-                (lookup.bucket("donation_amount_2"),)
+                (
+                    lookup.bucket("donation_amount_2"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
             })
             .assert_worktop_contains(base.membership_resource_address, dec!(2))
             .take_all_from_worktop(base.membership_resource_address, "memberships")
@@ -998,4 +1113,3357 @@ mod tests {
             dec!(10000)
         );
     }
+
+    #[test]
+    fn merge_trophies_grouped_success() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        // Create two collection components
+        let first_collection =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+        let second_collection =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Donate twice to each collection, and merge all trophies grouped by collection
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(400))
+            .take_from_worktop(XRD, dec!(100), "donation_amount_1")
+            .call_method_with_name_lookup(first_collection, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount_1"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .take_from_worktop(XRD, dec!(100), "donation_amount_2")
+            .call_method_with_name_lookup(first_collection, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount_2"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .take_from_worktop(XRD, dec!(50), "donation_amount_3")
+            .call_method_with_name_lookup(second_collection, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount_3"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .take_from_worktop(XRD, dec!(150), "donation_amount_4")
+            .call_method_with_name_lookup(second_collection, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount_4"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .assert_worktop_contains(base.trophy_resource_address, dec!(4))
+            .take_all_from_worktop(base.trophy_resource_address, "trophies")
+            .call_method_with_name_lookup(
+                base.repository_component,
+                "merge_trophies_grouped",
+                |lookup| (lookup.bucket("trophies"),),
+            )
+            .assert_worktop_contains(base.trophy_resource_address, dec!(2))
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        let trophy_vault = base.test_runner.get_component_vaults(
+            donation_account.wallet_address,
+            base.trophy_resource_address,
+        );
+
+        let trophy_ids: Vec<NonFungibleLocalId> = base
+            .test_runner
+            .inspect_non_fungible_vault(trophy_vault[0])
+            .unwrap()
+            .1
+            .collect();
+
+        assert_eq!(trophy_ids.len(), 2);
+
+        for trophy_id in trophy_ids {
+            let trophy_data: Trophy = base
+                .test_runner
+                .get_non_fungible_data(base.trophy_resource_address, trophy_id);
+
+            assert_eq!(trophy_data.donated, dec!(200));
+            assert_eq!(trophy_data.transactions.len(), 2);
+        }
+    }
+
+    #[test]
+    fn preview_merge_success() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Donate twice, and preview the merge of the trophies
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(150))
+            .take_from_worktop(XRD, dec!(100), "donation_amount_1")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount_1"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .take_from_worktop(XRD, dec!(50), "donation_amount_2")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount_2"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .take_all_from_worktop(base.trophy_resource_address, "trophies")
+            .create_proof_from_bucket_of_all("trophies", "trophies_proof")
+            .call_method_with_name_lookup(base.repository_component, "preview_merge", |lookup| {
+                (lookup.proof("trophies_proof"),)
+            })
+            .return_to_worktop("trophies")
+            .assert_worktop_contains(base.trophy_resource_address, dec!(2))
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        let (donated, created): (Decimal, String) = receipt.expect_commit_success().output(7);
+
+        assert_eq!(donated, dec!(150));
+        assert_eq!(created, "2023-11-04T10:19");
+    }
+
+    #[test]
+    fn preview_merge_success_mixed_granularity() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Donate and mint a trophy with a minute created date
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(100))
+            .take_from_worktop(XRD, dec!(100), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        // Only keep the day of the created date of new trophies
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                creator_badge_account.wallet_address,
+                creator_badge_badge_id,
+            )
+            .call_method(
+                collection_component,
+                "set_created_granularity",
+                manifest_args!(CreatedGranularity::Day),
+            );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        // Donate a trophy with a day created date, and preview the merge with the first one
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(50))
+            .take_from_worktop(XRD, dec!(50), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .withdraw_from_account(
+                donation_account.wallet_address,
+                base.trophy_resource_address,
+                dec!(1),
+            )
+            .take_all_from_worktop(base.trophy_resource_address, "trophies")
+            .create_proof_from_bucket_of_all("trophies", "trophies_proof")
+            .call_method_with_name_lookup(base.repository_component, "preview_merge", |lookup| {
+                (lookup.proof("trophies_proof"),)
+            })
+            .return_to_worktop("trophies")
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        // The merged trophy has a minute created date, as one of the trophies has
+        let (donated, created): (Decimal, String) = receipt.expect_commit_success().output(6);
+
+        assert_eq!(donated, dec!(150));
+        assert_eq!(created, "2023-11-04T00:00");
+    }
+
+    #[test]
+    fn split_trophy_success() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Donate, mint trophy and split it into two trophies
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(150))
+            .take_from_worktop(XRD, dec!(150), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .take_all_from_worktop(base.trophy_resource_address, "trophy")
+            .call_method_with_name_lookup(base.repository_component, "split_trophy", |lookup| {
+                (lookup.bucket("trophy"), vec![dec!(100), dec!(50)])
+            })
+            .assert_worktop_contains(base.trophy_resource_address, dec!(2))
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        let trophy_vault = base.test_runner.get_component_vaults(
+            donation_account.wallet_address,
+            base.trophy_resource_address,
+        );
+
+        let trophy_ids: Vec<NonFungibleLocalId> = base
+            .test_runner
+            .inspect_non_fungible_vault(trophy_vault[0])
+            .unwrap()
+            .1
+            .collect();
+
+        assert_eq!(trophy_ids.len(), 2);
+
+        let mut donated = dec!(0);
+        for trophy_id in trophy_ids {
+            let trophy_data: Trophy = base
+                .test_runner
+                .get_non_fungible_data(base.trophy_resource_address, trophy_id);
+
+            assert_eq!(trophy_data.created, "2023-11-04T10:19");
+            assert_eq!(trophy_data.transactions.len(), 1);
+            assert!(!trophy_data.refundable);
+            donated += trophy_data.donated;
+        }
+
+        assert_eq!(donated, dec!(150));
+    }
+
+    #[test]
+    fn split_trophy_failure_amount_mismatch() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Donate, mint trophy and attempt to split it into more than was donated
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(150))
+            .take_from_worktop(XRD, dec!(150), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .take_all_from_worktop(base.trophy_resource_address, "trophy")
+            .call_method_with_name_lookup(base.repository_component, "split_trophy", |lookup| {
+                (lookup.bucket("trophy"), vec![dec!(100), dec!(100)])
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_failure();
+    }
+
+    #[test]
+    fn split_trophy_failure_too_many() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Donate, mint trophy and attempt to split it into more trophies than can be merged
+        let shards = MAX_MERGE_SIZE as u64 + 1;
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, Decimal::from(shards))
+            .take_from_worktop(XRD, Decimal::from(shards), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .take_all_from_worktop(base.trophy_resource_address, "trophy")
+            .call_method_with_name_lookup(base.repository_component, "split_trophy", |lookup| {
+                (lookup.bucket("trophy"), vec![dec!(1); shards as usize])
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_failure();
+    }
+
+    #[test]
+    fn merge_trophies_failure_too_many() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Mint one trophy more than can be merged, by splitting a trophy into as many trophies as
+        // can be merged and minting another one
+        let shards = MAX_MERGE_SIZE as u64;
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(
+                donation_account.wallet_address,
+                XRD,
+                Decimal::from(shards) + dec!(10),
+            )
+            .take_from_worktop(XRD, Decimal::from(shards), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .take_all_from_worktop(base.trophy_resource_address, "trophy")
+            .call_method_with_name_lookup(base.repository_component, "split_trophy", |lookup| {
+                (lookup.bucket("trophy"), vec![dec!(1); shards as usize])
+            })
+            .take_from_worktop(XRD, dec!(10), "second_donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("second_donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        assert_eq!(
+            base.test_runner.get_component_balance(
+                donation_account.wallet_address,
+                base.trophy_resource_address
+            ),
+            Decimal::from(MAX_MERGE_SIZE as u64 + 1)
+        );
+
+        // Attempt to merge all of the trophies at once
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(
+                donation_account.wallet_address,
+                base.trophy_resource_address,
+                Decimal::from(MAX_MERGE_SIZE as u64 + 1),
+            )
+            .take_all_from_worktop(base.trophy_resource_address, "trophies")
+            .call_method_with_name_lookup(base.repository_component, "merge_trophies", |lookup| {
+                (lookup.bucket("trophies"),)
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_failure();
+
+        // Attempt to merge all of the trophies at once, grouped by collection
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(
+                donation_account.wallet_address,
+                base.trophy_resource_address,
+                Decimal::from(MAX_MERGE_SIZE as u64 + 1),
+            )
+            .take_all_from_worktop(base.trophy_resource_address, "trophies")
+            .call_method_with_name_lookup(
+                base.repository_component,
+                "merge_trophies_grouped",
+                |lookup| (lookup.bucket("trophies"),),
+            )
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_failure();
+    }
+
+    #[test]
+    fn get_collection_success() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        let collection_id = AddressBech32Encoder::new(&NetworkDefinition::simulator())
+            .encode(&collection_component.to_vec())
+            .unwrap();
+
+        // Look up the collection by its id, and an unknown id
+        let manifest = ManifestBuilder::new()
+            .call_method(
+                base.repository_component,
+                "get_collection",
+                manifest_args!(collection_id),
+            )
+            .call_method(
+                base.repository_component,
+                "get_collection",
+                manifest_args!("unknown"),
+            );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account.public_key,
+            )],
+            true,
+        );
+
+        let result = receipt.expect_commit_success();
+        let found: Option<ComponentAddress> = result.output(0);
+        let not_found: Option<ComponentAddress> = result.output(1);
+
+        assert_eq!(found, Some(collection_component));
+        assert_eq!(not_found, None);
+    }
+
+    #[test]
+    fn revoke_collection_minter_success() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Revoke the minter badge of the collection
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                base.owner_account.wallet_address,
+                base.repository_owner_badge_global_id.clone(),
+            )
+            .call_method(
+                base.repository_component,
+                "revoke_collection_minter",
+                manifest_args!(collection_component),
+            );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &base.owner_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        // Donations can no longer mint trophies
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(100))
+            .take_from_worktop(XRD, dec!(100), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_failure();
+    }
+
+    #[test]
+    fn revoke_minter_failure_auth() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Attempt to revoke the minter badge directly, without going through the repository
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                base.owner_account.wallet_address,
+                base.repository_owner_badge_global_id.clone(),
+            )
+            .call_method(collection_component, "revoke_minter", manifest_args!());
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &base.owner_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_failure();
+    }
+
+    #[test]
+    fn get_trophy_success() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Donate and mint trophy
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(150))
+            .take_from_worktop(XRD, dec!(150), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        let trophy_vault = base.test_runner.get_component_vaults(
+            donation_account.wallet_address,
+            base.trophy_resource_address,
+        );
+
+        let trophy_id = base
+            .test_runner
+            .inspect_non_fungible_vault(trophy_vault[0])
+            .unwrap()
+            .1
+            .next()
+            .unwrap();
+
+        // Read the trophy through the repository
+        let manifest = ManifestBuilder::new().call_method(
+            base.repository_component,
+            "get_trophy",
+            manifest_args!(trophy_id),
+        );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        let trophy_data: Trophy = receipt.expect_commit_success().output(0);
+        assert_eq!(trophy_data.donated, dec!(150));
+        assert_eq!(trophy_data.name, "Trophy name");
+    }
+
+    #[test]
+    fn update_dapp_definition_success() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Create the new dapp definition account
+        let dapp_definition_account = new_account(&mut base.test_runner);
+        let new_address = GlobalAddress::from(dapp_definition_account.wallet_address);
+
+        // Update the dapp definition of the repository, and of the collection
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                base.owner_account.wallet_address,
+                base.repository_owner_badge_global_id.clone(),
+            )
+            .call_method(
+                base.repository_component,
+                "update_dapp_definition",
+                manifest_args!(new_address),
+            )
+            .call_method(
+                base.repository_component,
+                "update_collections_dapp_definition",
+                manifest_args!(vec![collection_component]),
+            );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &base.owner_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        for address in [
+            GlobalAddress::from(base.repository_component),
+            GlobalAddress::from(base.trophy_resource_address),
+            GlobalAddress::from(collection_component),
+        ] {
+            assert_eq!(
+                base.test_runner.get_metadata(address, "dapp_definition"),
+                Some(MetadataValue::GlobalAddress(new_address))
+            );
+        }
+
+        // Read the dapp definition through the view methods
+        let manifest = ManifestBuilder::new()
+            .call_method(
+                base.repository_component,
+                "get_dapp_definition",
+                manifest_args!(),
+            )
+            .call_method(
+                collection_component,
+                "get_dapp_definition",
+                manifest_args!(),
+            );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &base.owner_account.public_key,
+            )],
+            true,
+        );
+
+        let commit = receipt.expect_commit_success();
+        let repository_dapp_definition: GlobalAddress = commit.output(0);
+        let collection_dapp_definition: GlobalAddress = commit.output(1);
+        assert_eq!(repository_dapp_definition, new_address);
+        assert_eq!(collection_dapp_definition, new_address);
+    }
+
+    #[test]
+    fn burn_trophy_success() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Donate, mint trophy and burn it
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(150))
+            .take_from_worktop(XRD, dec!(150), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .take_all_from_worktop(base.trophy_resource_address, "trophy")
+            .call_method_with_name_lookup(base.repository_component, "burn_trophy", |lookup| {
+                (lookup.bucket("trophy"),)
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        assert_eq!(
+            base.test_runner.get_component_balance(
+                donation_account.wallet_address,
+                base.trophy_resource_address
+            ),
+            dec!(0)
+        );
+    }
+
+    #[test]
+    fn burn_trophy_failure_wrong_resource() {
+        let mut base = new_runner();
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        // Attempt to burn something that is not a trophy
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(10))
+            .take_from_worktop(XRD, dec!(10), "not_a_trophy")
+            .call_method_with_name_lookup(base.repository_component, "burn_trophy", |lookup| {
+                (lookup.bucket("not_a_trophy"),)
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_failure();
+    }
+
+    #[test]
+    fn update_trophy_names_success() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Donate and mint trophy
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(150))
+            .take_from_worktop(XRD, dec!(150), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        let trophy_vault = base.test_runner.get_component_vaults(
+            donation_account.wallet_address,
+            base.trophy_resource_address,
+        );
+
+        let trophy_id = base
+            .test_runner
+            .inspect_non_fungible_vault(trophy_vault[0])
+            .unwrap()
+            .1
+            .next()
+            .unwrap();
+
+        // Rename the trophy with the repository owner badge
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                base.owner_account.wallet_address,
+                base.repository_owner_badge_global_id.clone(),
+            )
+            .call_method(
+                base.repository_component,
+                "update_trophy_names",
+                manifest_args!(vec![trophy_id.clone()], "New trophy name"),
+            );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &base.owner_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        let trophy_data: Trophy = base
+            .test_runner
+            .get_non_fungible_data(base.trophy_resource_address, trophy_id);
+
+        assert_eq!(trophy_data.name, "New trophy name");
+    }
+
+    #[test]
+    fn update_base_path_success() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Donate and mint trophy
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(150))
+            .take_from_worktop(XRD, dec!(150), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        let trophy_vault = base.test_runner.get_component_vaults(
+            donation_account.wallet_address,
+            base.trophy_resource_address,
+        );
+
+        let trophy_id = base
+            .test_runner
+            .inspect_non_fungible_vault(trophy_vault[0])
+            .unwrap()
+            .1
+            .next()
+            .unwrap();
+
+        // Update the base path with the repository owner badge
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                base.owner_account.wallet_address,
+                base.repository_owner_badge_global_id.clone(),
+            )
+            .call_method(
+                base.repository_component,
+                "update_base_path",
+                manifest_args!("https://backeum.com", vec![trophy_id.clone()]),
+            );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &base.owner_account.public_key,
+            )],
+            true,
+        );
+
+        let updated: u64 = receipt.expect_commit_success().output(1);
+        assert_eq!(updated, 1);
+
+        let trophy_data: Trophy = base
+            .test_runner
+            .get_non_fungible_data(base.trophy_resource_address, trophy_id);
+
+        assert_eq!(
+            trophy_data.key_image_url,
+            UncheckedUrl::of(format!(
+                "https://backeum.com/nft/collection/{}?donated=150&created=2023-11-04T10:19&currency={}&tier=silver",
+                trophy_data.collection_id,
+                AddressBech32Encoder::new(&NetworkDefinition::simulator())
+                    .encode(&XRD.to_vec())
+                    .unwrap()
+            ))
+        );
+        assert_eq!(
+            trophy_data.info_url,
+            UncheckedUrl::of("https://backeum.com/p/kansuler".to_owned())
+        );
+
+        // Attempt to update more trophies than allowed in a single batch
+        let too_many_ids: Vec<NonFungibleLocalId> =
+            (1..=101).map(NonFungibleLocalId::integer).collect();
+
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                base.owner_account.wallet_address,
+                base.repository_owner_badge_global_id.clone(),
+            )
+            .call_method(
+                base.repository_component,
+                "update_base_path",
+                manifest_args!("https://backeum.com", too_many_ids),
+            );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &base.owner_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_failure();
+    }
+
+    #[test]
+    fn update_base_path_success_custom_info_url() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        // Create a collection component with a custom info url ending like a default info url
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                creator_badge_account.wallet_address,
+                creator_badge_badge_id.clone(),
+            )
+            .pop_from_auth_zone("creator_badge_proof")
+            .call_method_with_name_lookup(
+                base.repository_component,
+                "new_collection_component",
+                |lookup| {
+                    (
+                        lookup.proof("creator_badge_proof"),
+                        "Trophy name",
+                        "Kansulers trophy",
+                        vec![XRD],
+                        Some("https://creator.art/p/kansuler".to_owned()),
+                        None::<Decimal>,
+                    )
+                },
+            );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account.public_key,
+            )],
+            true,
+        );
+
+        let custom_collection_component =
+            receipt.expect_commit_success().new_component_addresses()[0];
+
+        let default_collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Donate and mint a trophy of the custom info url collection
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(150))
+            .take_from_worktop(XRD, dec!(150), "donation_amount")
+            .call_method_with_name_lookup(custom_collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        let trophy_vault = base.test_runner.get_component_vaults(
+            donation_account.wallet_address,
+            base.trophy_resource_address,
+        );
+
+        let custom_trophy_id = base
+            .test_runner
+            .inspect_non_fungible_vault(trophy_vault[0])
+            .unwrap()
+            .1
+            .next()
+            .unwrap();
+
+        // Donate and mint a trophy of the default info url collection
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(150))
+            .take_from_worktop(XRD, dec!(150), "donation_amount")
+            .call_method_with_name_lookup(default_collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        let default_trophy_id = base
+            .test_runner
+            .inspect_non_fungible_vault(trophy_vault[0])
+            .unwrap()
+            .1
+            .find(|trophy_id| *trophy_id != custom_trophy_id)
+            .unwrap();
+
+        // Update the base path one trophy at a time
+        for (i, trophy_id) in [custom_trophy_id.clone(), default_trophy_id.clone()]
+            .into_iter()
+            .enumerate()
+        {
+            let manifest = ManifestBuilder::new()
+                .create_proof_from_account_of_non_fungible(
+                    base.owner_account.wallet_address,
+                    base.repository_owner_badge_global_id.clone(),
+                )
+                .call_method(
+                    base.repository_component,
+                    "update_base_path",
+                    manifest_args!("https://backeum.com", vec![trophy_id]),
+                );
+
+            let receipt = execute_manifest(
+                &mut base.test_runner,
+                manifest,
+                &format!("update_base_path_success_custom_info_url_{}", i + 4),
+                vec![NonFungibleGlobalId::from_public_key(
+                    &base.owner_account.public_key,
+                )],
+                true,
+            );
+
+            receipt.expect_commit_success();
+        }
+
+        // The custom info url is kept, and the default info url is updated with the image url
+        let custom_trophy_data: Trophy = base
+            .test_runner
+            .get_non_fungible_data(base.trophy_resource_address, custom_trophy_id);
+
+        assert_eq!(
+            custom_trophy_data.info_url,
+            UncheckedUrl::of("https://creator.art/p/kansuler".to_owned())
+        );
+        assert!(custom_trophy_data
+            .key_image_url
+            .as_str()
+            .starts_with("https://backeum.com/nft/collection/"));
+
+        let default_trophy_data: Trophy = base
+            .test_runner
+            .get_non_fungible_data(base.trophy_resource_address, default_trophy_id);
+
+        assert_eq!(
+            default_trophy_data.info_url,
+            UncheckedUrl::of("https://backeum.com/p/kansuler".to_owned())
+        );
+        assert!(default_trophy_data
+            .key_image_url
+            .as_str()
+            .starts_with("https://backeum.com/nft/collection/"));
+    }
+
+    #[test]
+    fn merge_trophies_failure_empty() {
+        let mut base = new_runner();
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        // Attempt to merge an empty bucket of trophies
+        let manifest = ManifestBuilder::new()
+            .take_all_from_worktop(base.trophy_resource_address, "trophies")
+            .call_method_with_name_lookup(base.repository_component, "merge_trophies", |lookup| {
+                (lookup.bucket("trophies"),)
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_failure();
+    }
+
+    #[test]
+    fn merge_trophies_failure_single() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Donate, mint trophy and attempt to merge it on its own
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(250))
+            .take_from_worktop(XRD, dec!(250), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .take_all_from_worktop(base.trophy_resource_address, "trophies")
+            .call_method_with_name_lookup(base.repository_component, "merge_trophies", |lookup| {
+                (lookup.bucket("trophies"),)
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_failure();
+    }
+
+    #[test]
+    fn get_trophy_resource_address_success() {
+        let mut base = new_runner();
+
+        // Read the trophy resource address from the repository
+        let manifest = ManifestBuilder::new().call_method(
+            base.repository_component,
+            "get_trophy_resource_address",
+            manifest_args!(),
+        );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &base.owner_account.public_key,
+            )],
+            true,
+        );
+
+        let trophy_resource_address: ResourceAddress = receipt.expect_commit_success().output(0);
+        assert_eq!(trophy_resource_address, base.trophy_resource_address);
+    }
+
+    #[test]
+    fn new_collection_component_and_badge_team_success() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+
+        // Create a collection component with three admin badges
+        let manifest = ManifestBuilder::new()
+            .call_method(
+                base.repository_component,
+                "new_collection_component_and_badge",
+                manifest_args!(
+                    "Kansuler",
+                    "kansuler",
+                    "Trophy name",
+                    "Trophy description",
+                    vec![XRD],
+                    None::<String>,
+                    None::<Decimal>,
+                    3u8,
+                ),
+            )
+            .assert_worktop_contains(base.creator_badge_resource_address, dec!(3))
+            .deposit_batch(creator_badge_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account.public_key,
+            )],
+            true,
+        );
+
+        let collection_component = receipt.expect_commit_success().new_component_addresses()[0];
+
+        let creator_badge_vault = base.test_runner.get_component_vaults(
+            creator_badge_account.wallet_address,
+            base.creator_badge_resource_address,
+        );
+
+        let creator_badge_local_ids: Vec<NonFungibleLocalId> = base
+            .test_runner
+            .inspect_non_fungible_vault(creator_badge_vault[0])
+            .unwrap()
+            .1
+            .collect();
+
+        assert_eq!(creator_badge_local_ids.len(), 3);
+
+        // Withdraw with each of the admin badges
+        for (i, local_id) in creator_badge_local_ids.into_iter().enumerate() {
+            let manifest = ManifestBuilder::new()
+                .withdraw_from_account(creator_badge_account.wallet_address, XRD, dec!(10))
+                .take_from_worktop(XRD, dec!(10), "donation_amount")
+                .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                    (
+                        lookup.bucket("donation_amount"),
+                        None::<ComponentAddress>,
+                        None::<String>,
+                        false,
+                        None::<String>,
+                    )
+                })
+                .create_proof_from_account_of_non_fungible(
+                    creator_badge_account.wallet_address,
+                    NonFungibleGlobalId::new(base.creator_badge_resource_address, local_id),
+                )
+                .call_method(
+                    collection_component,
+                    "withdraw_donations",
+                    manifest_args!(XRD),
+                )
+                .deposit_batch(creator_badge_account.wallet_address);
+
+            let receipt = execute_manifest(
+                &mut base.test_runner,
+                manifest,
+                &format!("new_collection_component_and_badge_team_success_{}", i + 2),
+                vec![NonFungibleGlobalId::from_public_key(
+                    &creator_badge_account.public_key,
+                )],
+                true,
+            );
+
+            receipt.expect_commit_success();
+        }
+    }
+
+    #[test]
+    fn merge_trophies_success_transactions() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        // Mint the first two trophies on 2023-11-04
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(200))
+            .take_from_worktop(XRD, dec!(100), "donation_amount_1")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount_1"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .take_from_worktop(XRD, dec!(100), "donation_amount_2")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount_2"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        let trophy_vault = base.test_runner.get_component_vaults(
+            donation_account.wallet_address,
+            base.trophy_resource_address,
+        );
+
+        let first_trophy_ids: Vec<NonFungibleLocalId> = base
+            .test_runner
+            .inspect_non_fungible_vault(trophy_vault[0])
+            .unwrap()
+            .1
+            .collect();
+
+        // Mint the third trophy and top up the first trophy on 2023-11-05
+        base.test_runner
+            .advance_to_round_at_timestamp(Round::of(2), 1699142400000); // 2023-11-05
+
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(150))
+            .take_from_worktop(XRD, dec!(100), "donation_amount_1")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount_1"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .take_from_worktop(XRD, dec!(50), "donation_amount_2")
+            .call_method_with_name_lookup(collection_component, "donate_update_by_id", |lookup| {
+                (
+                    lookup.bucket("donation_amount_2"),
+                    first_trophy_ids[0].clone(),
+                )
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        let trophy_ids: Vec<NonFungibleLocalId> = base
+            .test_runner
+            .inspect_non_fungible_vault(trophy_vault[0])
+            .unwrap()
+            .1
+            .collect();
+
+        let third_trophy_id = trophy_ids
+            .into_iter()
+            .find(|id| !first_trophy_ids.contains(id))
+            .unwrap();
+
+        // Top up the second and third trophy on 2023-11-06
+        base.test_runner
+            .advance_to_round_at_timestamp(Round::of(3), 1699228800000); // 2023-11-06
+
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(50))
+            .take_from_worktop(XRD, dec!(25), "donation_amount_1")
+            .call_method_with_name_lookup(collection_component, "donate_update_by_id", |lookup| {
+                (lookup.bucket("donation_amount_1"), third_trophy_id.clone())
+            })
+            .take_from_worktop(XRD, dec!(25), "donation_amount_2")
+            .call_method_with_name_lookup(collection_component, "donate_update_by_id", |lookup| {
+                (
+                    lookup.bucket("donation_amount_2"),
+                    first_trophy_ids[1].clone(),
+                )
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        // Merge all three trophies
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(
+                donation_account.wallet_address,
+                base.trophy_resource_address,
+                dec!(3),
+            )
+            .take_all_from_worktop(base.trophy_resource_address, "trophies")
+            .call_method_with_name_lookup(base.repository_component, "merge_trophies", |lookup| {
+                (lookup.bucket("trophies"),)
+            })
+            .assert_worktop_contains(base.trophy_resource_address, dec!(1))
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        let trophy_id: NonFungibleLocalId = base
+            .test_runner
+            .inspect_non_fungible_vault(trophy_vault[0])
+            .unwrap()
+            .1
+            .next()
+            .unwrap();
+
+        let trophy_data: Trophy = base
+            .test_runner
+            .get_non_fungible_data(base.trophy_resource_address, trophy_id);
+
+        assert_eq!(trophy_data.donated, dec!(400));
+        assert_eq!(trophy_data.created, "2023-11-04T10:19");
+
+        // The transactions are sorted by date, the order within the same date follows the order
+        // of the merged trophies.
+        let created: Vec<String> = trophy_data
+            .transactions
+            .iter()
+            .map(|transaction| transaction.created.clone())
+            .collect();
+
+        assert_eq!(
+            created,
+            vec![
+                "2023-11-04T10:19",
+                "2023-11-04T10:19",
+                "2023-11-05T00:00",
+                "2023-11-05T00:00",
+                "2023-11-06T00:00",
+                "2023-11-06T00:00",
+            ]
+        );
+
+        let mut amounts: Vec<Decimal> = trophy_data
+            .transactions
+            .iter()
+            .map(|transaction| transaction.amount)
+            .collect();
+        amounts.sort();
+
+        assert_eq!(
+            amounts,
+            vec![
+                dec!(25),
+                dec!(25),
+                dec!(50),
+                dec!(100),
+                dec!(100),
+                dec!(100)
+            ]
+        );
+    }
+
+    #[test]
+    fn get_collection_trophies_success() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        let collection_id = AddressBech32Encoder::new(&NetworkDefinition::simulator())
+            .encode(&collection_component.to_vec())
+            .unwrap();
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        // Donate and mint two trophies
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(100))
+            .take_from_worktop(XRD, dec!(50), "donation_amount_1")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount_1"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .take_from_worktop(XRD, dec!(50), "donation_amount_2")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount_2"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        let trophy_vault = base.test_runner.get_component_vaults(
+            donation_account.wallet_address,
+            base.trophy_resource_address,
+        );
+
+        let mut trophy_ids: Vec<NonFungibleLocalId> = base
+            .test_runner
+            .inspect_non_fungible_vault(trophy_vault[0])
+            .unwrap()
+            .1
+            .collect();
+        trophy_ids.sort();
+
+        // Read the trophy index of the collection one trophy at a time
+        let manifest = ManifestBuilder::new()
+            .call_method(
+                base.repository_component,
+                "get_collection_trophy_count",
+                manifest_args!(collection_id.clone()),
+            )
+            .call_method(
+                base.repository_component,
+                "get_collection_trophies",
+                manifest_args!(collection_id.clone(), 0u64, 1u64),
+            )
+            .call_method(
+                base.repository_component,
+                "get_collection_trophies",
+                manifest_args!(collection_id.clone(), 1u64, 1u64),
+            )
+            .call_method(
+                base.repository_component,
+                "get_collection_trophies",
+                manifest_args!(collection_id.clone(), 2u64, 1u64),
+            );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        let result = receipt.expect_commit_success();
+        let count: u64 = result.output(0);
+        let first_page: Vec<NonFungibleLocalId> = result.output(1);
+        let second_page: Vec<NonFungibleLocalId> = result.output(2);
+        let last_page: Vec<NonFungibleLocalId> = result.output(3);
+
+        assert_eq!(count, 2);
+        assert_eq!(first_page.len(), 1);
+        assert_eq!(second_page.len(), 1);
+        assert!(last_page.is_empty());
+
+        let mut indexed_ids = [first_page, second_page].concat();
+        indexed_ids.sort();
+
+        assert_eq!(indexed_ids, trophy_ids);
+
+        // A collection without trophies has an empty index
+        let manifest = ManifestBuilder::new().call_method(
+            base.repository_component,
+            "get_collection_trophies",
+            manifest_args!("unknown", 0u64, 100u64),
+        );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        let indexed_ids: Vec<NonFungibleLocalId> = receipt.expect_commit_success().output(0);
+        assert!(indexed_ids.is_empty());
+    }
+
+    #[test]
+    fn get_collection_trophies_failure_limit() {
+        let mut base = new_runner();
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        // Read more trophies than allowed at a time
+        let manifest = ManifestBuilder::new().call_method(
+            base.repository_component,
+            "get_collection_trophies",
+            manifest_args!("unknown", 0u64, 101u64),
+        );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_failure();
+    }
+
+    #[test]
+    fn index_trophy_failure_no_minter_badge() {
+        let mut base = new_runner();
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        // Index a trophy without the minter badge
+        let manifest = ManifestBuilder::new().call_method(
+            base.repository_component,
+            "index_trophy",
+            manifest_args!(NonFungibleLocalId::integer(1)),
+        );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_failure();
+    }
+
+    #[test]
+    fn export_trophy_success() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Donate and mint trophy
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(150))
+            .take_from_worktop(XRD, dec!(150), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        let trophy_vault = base.test_runner.get_component_vaults(
+            donation_account.wallet_address,
+            base.trophy_resource_address,
+        );
+
+        let trophy_id = base
+            .test_runner
+            .inspect_non_fungible_vault(trophy_vault[0])
+            .unwrap()
+            .1
+            .next()
+            .unwrap();
+
+        // Export the trophy through the repository
+        let manifest = ManifestBuilder::new().call_method(
+            base.repository_component,
+            "export_trophy",
+            manifest_args!(trophy_id),
+        );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        let exported: String = receipt.expect_commit_success().output(0);
+
+        let encoder = AddressBech32Encoder::new(&NetworkDefinition::simulator());
+        let collection_id = encoder.encode(&collection_component.to_vec()).unwrap();
+        let currency = encoder.encode(&XRD.to_vec()).unwrap();
+
+        assert_eq!(
+            exported,
+            format!(
+                "{{\"name\":\"Trophy name\",\"info_url\":\"https://localhost:8080/p/kansuler\",\"collection_id\":\"{}\",\"created\":\"2023-11-04T10:19\",\"donated\":\"150\",\"key_image_url\":\"https://localhost:8080/nft/collection/{}?donated=150&created=2023-11-04T10:19&currency={}&tier=silver\"}}",
+                collection_id, collection_id, currency
+            )
+        );
+    }
+
+    #[test]
+    fn get_collection_total_success() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        let collection_id = AddressBech32Encoder::new(&NetworkDefinition::simulator())
+            .encode(&collection_component.to_vec())
+            .unwrap();
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        // Donate twice and read the total of the collection
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(150))
+            .take_from_worktop(XRD, dec!(100), "donation_amount_1")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount_1"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .take_from_worktop(XRD, dec!(50), "donation_amount_2")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount_2"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .call_method(
+                base.repository_component,
+                "get_collection_total",
+                manifest_args!(collection_id),
+            )
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        let total: Decimal = receipt.expect_commit_success().output(5);
+        assert_eq!(total, dec!(150));
+    }
+
+    #[test]
+    fn update_collection_total_failure_no_minter_badge() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        let collection_id = AddressBech32Encoder::new(&NetworkDefinition::simulator())
+            .encode(&collection_component.to_vec())
+            .unwrap();
+
+        // Attempt to report a total without the minter badge
+        let manifest = ManifestBuilder::new().call_method(
+            base.repository_component,
+            "update_collection_total",
+            manifest_args!(collection_id, dec!(1000000)),
+        );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_failure();
+    }
+
+    #[test]
+    fn new_collection_component_failure_slug_of_other_creator() {
+        let mut base = new_runner();
+
+        // Create the component admin accounts of two creators with the same slug
+        let creator_badge_account_1 = new_account(&mut base.test_runner);
+        let creator_badge_badge_id_1 = mint_creator_badge(&mut base, &creator_badge_account_1);
+        let creator_badge_account_2 = new_account(&mut base.test_runner);
+        let creator_badge_badge_id_2 = mint_creator_badge(&mut base, &creator_badge_account_2);
+
+        // The first creator claims the slug with a collection, and can make another one with it
+        new_collection_component(
+            &mut base,
+            &creator_badge_account_1,
+            &creator_badge_badge_id_1,
+        );
+        new_collection_component(
+            &mut base,
+            &creator_badge_account_1,
+            &creator_badge_badge_id_1,
+        );
+
+        // Attempt to make a collection with the same slug as the second creator
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                creator_badge_account_2.wallet_address,
+                creator_badge_badge_id_2,
+            )
+            .pop_from_auth_zone("creator_badge_proof")
+            .call_method_with_name_lookup(
+                base.repository_component,
+                "new_collection_component",
+                |lookup| {
+                    (
+                        lookup.proof("creator_badge_proof"),
+                        "Trophy name",
+                        "Kansulers trophy",
+                        vec![XRD],
+                        None::<String>,
+                        None::<Decimal>,
+                    )
+                },
+            );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account_2.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_failure();
+
+        // Attempt to make a collection and creator badge with the same slug
+        let manifest = ManifestBuilder::new()
+            .call_method(
+                base.repository_component,
+                "new_collection_component_and_badge",
+                manifest_args!(
+                    "Kansuler",
+                    "kansuler",
+                    "Trophy name",
+                    "Kansulers trophy",
+                    vec![XRD],
+                    None::<String>,
+                    None::<Decimal>,
+                    1u8
+                ),
+            )
+            .deposit_batch(creator_badge_account_2.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account_2.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_failure();
+    }
+
+    #[test]
+    fn remap_collection_id_success() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        // Create the old and the new collection
+        let old_collection =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+        let new_collection =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        let new_collection_id = AddressBech32Encoder::new(&NetworkDefinition::simulator())
+            .encode(&new_collection.to_vec())
+            .unwrap();
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        // Donate and mint trophy in the old collection
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(150))
+            .take_from_worktop(XRD, dec!(150), "donation_amount")
+            .call_method_with_name_lookup(old_collection, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        let trophy_vault = base.test_runner.get_component_vaults(
+            donation_account.wallet_address,
+            base.trophy_resource_address,
+        );
+
+        let trophy_id = base
+            .test_runner
+            .inspect_non_fungible_vault(trophy_vault[0])
+            .unwrap()
+            .1
+            .next()
+            .unwrap();
+
+        // Move the trophy to the new collection
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                base.owner_account.wallet_address,
+                base.repository_owner_badge_global_id.clone(),
+            )
+            .call_method(
+                base.repository_component,
+                "remap_collection_id",
+                manifest_args!(vec![trophy_id.clone()], new_collection_id.clone()),
+            )
+            .call_method(
+                base.repository_component,
+                "get_collection_trophies",
+                manifest_args!(new_collection_id.clone(), 0u64, 100u64),
+            );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &base.owner_account.public_key,
+            )],
+            true,
+        );
+
+        let indexed_ids: Vec<NonFungibleLocalId> = receipt.expect_commit_success().output(2);
+        assert_eq!(indexed_ids, vec![trophy_id.clone()]);
+
+        let trophy_data: Trophy = base
+            .test_runner
+            .get_non_fungible_data(base.trophy_resource_address, trophy_id);
+
+        assert_eq!(trophy_data.collection_id, new_collection_id);
+        assert!(!trophy_data.refundable);
+        assert_eq!(
+            trophy_data.key_image_url,
+            UncheckedUrl::of(format!(
+                "https://localhost:8080/nft/collection/{}?donated=150&created=2023-11-04T10:19&currency={}&tier=silver",
+                new_collection_id,
+                AddressBech32Encoder::new(&NetworkDefinition::simulator())
+                    .encode(&XRD.to_vec())
+                    .unwrap()
+            ))
+        );
+    }
+
+    #[test]
+    fn remap_collection_id_failure_other_creator() {
+        let mut base = new_runner();
+
+        // Create the component admin accounts of two creators
+        let creator_badge_account_1 = new_account(&mut base.test_runner);
+        let creator_badge_badge_id_1 = mint_creator_badge(&mut base, &creator_badge_account_1);
+        let creator_badge_account_2 = new_account(&mut base.test_runner);
+        let creator_badge_badge_id_2 =
+            mint_creator_badge_with_slug(&mut base, &creator_badge_account_2, "kansuler-2");
+
+        // Create a collection for every creator
+        let old_collection = new_collection_component(
+            &mut base,
+            &creator_badge_account_1,
+            &creator_badge_badge_id_1,
+        );
+        let new_collection = new_collection_component(
+            &mut base,
+            &creator_badge_account_2,
+            &creator_badge_badge_id_2,
+        );
+
+        let new_collection_id = AddressBech32Encoder::new(&NetworkDefinition::simulator())
+            .encode(&new_collection.to_vec())
+            .unwrap();
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        // Donate and mint trophy in the collection of the first creator
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(150))
+            .take_from_worktop(XRD, dec!(150), "donation_amount")
+            .call_method_with_name_lookup(old_collection, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        let trophy_vault = base.test_runner.get_component_vaults(
+            donation_account.wallet_address,
+            base.trophy_resource_address,
+        );
+
+        let trophy_id = base
+            .test_runner
+            .inspect_non_fungible_vault(trophy_vault[0])
+            .unwrap()
+            .1
+            .next()
+            .unwrap();
+
+        // Attempt to move the trophy to the collection of the second creator
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                base.owner_account.wallet_address,
+                base.repository_owner_badge_global_id.clone(),
+            )
+            .call_method(
+                base.repository_component,
+                "remap_collection_id",
+                manifest_args!(vec![trophy_id.clone()], new_collection_id.clone()),
+            );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &base.owner_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_failure();
+
+        let trophy_data: Trophy = base
+            .test_runner
+            .get_non_fungible_data(base.trophy_resource_address, trophy_id);
+        assert_ne!(trophy_data.collection_id, new_collection_id);
+    }
+
+    #[test]
+    fn remap_collection_id_failure_unknown_collection() {
+        let mut base = new_runner();
+
+        // Attempt to move trophies to a collection that is not registered
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                base.owner_account.wallet_address,
+                base.repository_owner_badge_global_id.clone(),
+            )
+            .call_method(
+                base.repository_component,
+                "remap_collection_id",
+                manifest_args!(Vec::<NonFungibleLocalId>::new(), "unknown"),
+            );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &base.owner_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_failure();
+    }
+
+    #[test]
+    fn get_minter_badges_issued_success() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        // Create two collections, each holding a minter badge
+        new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+        new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Read the number of minter badges issued
+        let manifest = ManifestBuilder::new().call_method(
+            base.repository_component,
+            "get_minter_badges_issued",
+            manifest_args!(),
+        );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &base.owner_account.public_key,
+            )],
+            true,
+        );
+
+        let issued: u64 = receipt.expect_commit_success().output(0);
+        assert_eq!(issued, 2);
+    }
+
+    #[test]
+    fn new_collection_component_and_badge_failure_invalid_slug() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+
+        // Attempt to create a collection with a slug that is not url safe
+        let manifest = ManifestBuilder::new()
+            .call_method(
+                base.repository_component,
+                "new_collection_component_and_badge",
+                manifest_args!(
+                    "Kansuler",
+                    "kansuler?page=1",
+                    "Trophy name",
+                    "Trophy description",
+                    vec![XRD],
+                    None::<String>,
+                    None::<Decimal>,
+                    1u8,
+                ),
+            )
+            .deposit_batch(creator_badge_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_failure();
+    }
+
+    #[test]
+    fn mint_creator_badge_failure_invalid_slug() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+
+        // Attempt to mint a creator badge with a slug containing a slash
+        let manifest = ManifestBuilder::new()
+            .call_method(
+                base.repository_component,
+                "mint_creator_badge",
+                manifest_args!("Kansuler", "kansuler/page"),
+            )
+            .deposit_batch(creator_badge_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_failure();
+    }
+
+    #[test]
+    fn new_collection_and_donate_success() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+
+        // Create a collection and make the first donation in one transaction
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(creator_badge_account.wallet_address, XRD, dec!(100))
+            .take_from_worktop(XRD, dec!(100), "donation_amount")
+            .call_method_with_name_lookup(
+                base.repository_component,
+                "new_collection_and_donate",
+                |lookup| {
+                    (
+                        "Kansuler",
+                        "kansuler",
+                        "Trophy name",
+                        "Trophy description",
+                        lookup.bucket("donation_amount"),
+                    )
+                },
+            )
+            .assert_worktop_contains(base.trophy_resource_address, dec!(1))
+            .assert_worktop_contains(base.creator_badge_resource_address, dec!(1))
+            .assert_worktop_contains(base.thanks_token_resource_address, dec!(100))
+            .deposit_batch(creator_badge_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account.public_key,
+            )],
+            true,
+        );
+
+        let collection_component = receipt.expect_commit_success().new_component_addresses()[0];
+
+        let collection_id = AddressBech32Encoder::new(&NetworkDefinition::simulator())
+            .encode(&collection_component.to_vec())
+            .unwrap();
+
+        // The trophy and total are recorded, and the fee is taken from the donation
+        let manifest = ManifestBuilder::new()
+            .call_method(
+                base.repository_component,
+                "get_collection_trophies",
+                manifest_args!(collection_id.clone(), 0u64, 100u64),
+            )
+            .call_method(
+                base.repository_component,
+                "get_collection_total",
+                manifest_args!(collection_id),
+            )
+            .call_method(
+                collection_component,
+                "get_collection_info",
+                manifest_args!(),
+            );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account.public_key,
+            )],
+            true,
+        );
+
+        let commit = receipt.expect_commit_success();
+        let trophies: Vec<NonFungibleLocalId> = commit.output(1);
+        let total: Decimal = commit.output(2);
+        let info: CollectionInfo = commit.output(3);
+        assert_eq!(trophies.len(), 1);
+        assert_eq!(total, dec!(100));
+        assert_eq!(info.donations, vec![(XRD, dec!(96))]);
+    }
+
+    #[test]
+    fn set_frozen_success() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Freeze the repository
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                base.owner_account.wallet_address,
+                base.repository_owner_badge_global_id.clone(),
+            )
+            .call_method(
+                base.repository_component,
+                "set_frozen",
+                manifest_args!(true),
+            );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &base.owner_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        // New collections can not be made while frozen
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                creator_badge_account.wallet_address,
+                creator_badge_badge_id.clone(),
+            )
+            .pop_from_auth_zone("creator_badge_proof")
+            .call_method_with_name_lookup(
+                base.repository_component,
+                "new_collection_component",
+                |lookup| {
+                    (
+                        lookup.proof("creator_badge_proof"),
+                        "Trophy name",
+                        "Kansulers trophy",
+                        vec![XRD],
+                        None::<String>,
+                        None::<Decimal>,
+                    )
+                },
+            );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_failure();
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        // Existing collections still accept donations
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(100))
+            .take_from_worktop(XRD, dec!(100), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+    }
+
+    #[test]
+    fn set_frozen_failure_not_admin() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+
+        // Attempt to freeze the repository without the owner badge
+        let manifest = ManifestBuilder::new().call_method(
+            base.repository_component,
+            "set_frozen",
+            manifest_args!(true),
+        );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_failure();
+    }
+
+    #[test]
+    fn migrate_image_urls_success() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Donate and mint trophy
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(150))
+            .take_from_worktop(XRD, dec!(150), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        let trophy_vault = base.test_runner.get_component_vaults(
+            donation_account.wallet_address,
+            base.trophy_resource_address,
+        );
+
+        let trophy_id = base
+            .test_runner
+            .inspect_non_fungible_vault(trophy_vault[0])
+            .unwrap()
+            .1
+            .next()
+            .unwrap();
+
+        // Migrate the image url with the repository owner badge
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                base.owner_account.wallet_address,
+                base.repository_owner_badge_global_id.clone(),
+            )
+            .call_method(
+                base.repository_component,
+                "migrate_image_urls",
+                manifest_args!(vec![trophy_id.clone()]),
+            );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &base.owner_account.public_key,
+            )],
+            true,
+        );
+
+        let migrated: u64 = receipt.expect_commit_success().output(1);
+        assert_eq!(migrated, 1);
+
+        let trophy_data: Trophy = base
+            .test_runner
+            .get_non_fungible_data(base.trophy_resource_address, trophy_id);
+
+        assert_eq!(
+            trophy_data.key_image_url,
+            UncheckedUrl::of(format!(
+                "https://localhost:8080/nft/collection/{}?donated=150&created=2023-11-04T10:19&currency={}&tier=silver",
+                trophy_data.collection_id,
+                AddressBech32Encoder::new(&NetworkDefinition::simulator())
+                    .encode(&XRD.to_vec())
+                    .unwrap()
+            ))
+        );
+
+        // Attempt to migrate more trophies than allowed in a single batch
+        let too_many_ids: Vec<NonFungibleLocalId> =
+            (1..=101).map(NonFungibleLocalId::integer).collect();
+
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                base.owner_account.wallet_address,
+                base.repository_owner_badge_global_id.clone(),
+            )
+            .call_method(
+                base.repository_component,
+                "migrate_image_urls",
+                manifest_args!(too_many_ids),
+            );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &base.owner_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_failure();
+    }
+
+    #[test]
+    fn deregister_collection_success() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        let collection_id = AddressBech32Encoder::new(&NetworkDefinition::simulator())
+            .encode(&collection_component.to_vec())
+            .unwrap();
+
+        // Deregister the collection and look it up
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                base.owner_account.wallet_address,
+                base.repository_owner_badge_global_id.clone(),
+            )
+            .call_method(
+                base.repository_component,
+                "deregister_collection",
+                manifest_args!(collection_id.clone()),
+            )
+            .call_method(
+                base.repository_component,
+                "get_collection",
+                manifest_args!(collection_id),
+            );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &base.owner_account.public_key,
+            )],
+            true,
+        );
+
+        let found: Option<ComponentAddress> = receipt.expect_commit_success().output(2);
+        assert_eq!(found, None);
+
+        // The minter badge of the collection is kept
+        assert_eq!(
+            base.test_runner
+                .get_component_balance(collection_component, base.minter_badge_resource_address),
+            dec!(1)
+        );
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        // The deregistered collection no longer mints trophies
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(100))
+            .take_from_worktop(XRD, dec!(100), "donation_amount")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_failure();
+    }
+
+    #[test]
+    fn deregister_collection_success_revoked_minter() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        let collection_id = AddressBech32Encoder::new(&NetworkDefinition::simulator())
+            .encode(&collection_component.to_vec())
+            .unwrap();
+
+        // Revoke the minter badge of the collection and deregister it
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                base.owner_account.wallet_address,
+                base.repository_owner_badge_global_id.clone(),
+            )
+            .call_method(
+                base.repository_component,
+                "revoke_collection_minter",
+                manifest_args!(collection_component),
+            )
+            .call_method(
+                base.repository_component,
+                "deregister_collection",
+                manifest_args!(collection_id.clone()),
+            )
+            .call_method(
+                base.repository_component,
+                "get_collection",
+                manifest_args!(collection_id),
+            );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &base.owner_account.public_key,
+            )],
+            true,
+        );
+
+        let found: Option<ComponentAddress> = receipt.expect_commit_success().output(3);
+        assert_eq!(found, None);
+
+        // The minter badge of the collection is burned
+        assert_eq!(
+            base.test_runner
+                .get_component_balance(collection_component, base.minter_badge_resource_address),
+            dec!(0)
+        );
+    }
+
+    #[test]
+    fn deregister_collection_failure_not_admin() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        let collection_id = AddressBech32Encoder::new(&NetworkDefinition::simulator())
+            .encode(&collection_component.to_vec())
+            .unwrap();
+
+        // Attempt to deregister the collection without the owner badge
+        let manifest = ManifestBuilder::new().call_method(
+            base.repository_component,
+            "deregister_collection",
+            manifest_args!(collection_id),
+        );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_failure();
+    }
+
+    #[test]
+    fn merge_trophies_failure_fungible_resource() {
+        let mut base = new_runner();
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        // Attempt to merge a bucket of XRD
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(2))
+            .take_all_from_worktop(XRD, "trophies")
+            .call_method_with_name_lookup(base.repository_component, "merge_trophies", |lookup| {
+                (lookup.bucket("trophies"),)
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_failure();
+    }
+
+    #[test]
+    fn merge_trophies_failure_wrong_resource() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        mint_creator_badge(&mut base, &creator_badge_account);
+
+        // Attempt to merge creator badges instead of trophies
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(
+                creator_badge_account.wallet_address,
+                base.creator_badge_resource_address,
+                dec!(1),
+            )
+            .take_all_from_worktop(base.creator_badge_resource_address, "trophies")
+            .call_method_with_name_lookup(base.repository_component, "merge_trophies", |lookup| {
+                (lookup.bucket("trophies"),)
+            })
+            .deposit_batch(creator_badge_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &creator_badge_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_failure();
+    }
+
+    #[test]
+    fn filter_trophies_success() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        let collection_component =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Donate twice and mint two trophies
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(200))
+            .take_from_worktop(XRD, dec!(50), "donation_amount_1")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount_1"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .take_from_worktop(XRD, dec!(150), "donation_amount_2")
+            .call_method_with_name_lookup(collection_component, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount_2"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        receipt.expect_commit_success();
+
+        let trophy_vault = base.test_runner.get_component_vaults(
+            donation_account.wallet_address,
+            base.trophy_resource_address,
+        );
+
+        let trophy_ids: Vec<NonFungibleLocalId> = base
+            .test_runner
+            .inspect_non_fungible_vault(trophy_vault[0])
+            .unwrap()
+            .1
+            .collect();
+
+        let expected: Vec<NonFungibleLocalId> = trophy_ids
+            .iter()
+            .filter(|trophy_id| {
+                let trophy_data: Trophy = base
+                    .test_runner
+                    .get_non_fungible_data(base.trophy_resource_address, (*trophy_id).clone());
+                trophy_data.donated == dec!(150)
+            })
+            .cloned()
+            .collect();
+
+        // Filter the trophies, together with an id that does not exist
+        let mut filter_ids = trophy_ids.clone();
+        filter_ids.push(NonFungibleLocalId::ruid([1u8; 32]));
+
+        let manifest = ManifestBuilder::new().call_method(
+            base.repository_component,
+            "filter_trophies",
+            manifest_args!(filter_ids, dec!(100)),
+        );
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        let filtered: Vec<NonFungibleLocalId> = receipt.expect_commit_success().output(0);
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered, expected);
+    }
+
+    #[test]
+    fn sum_donations_success() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        // Create donation account
+        let donation_account = new_account(&mut base.test_runner);
+
+        // Create two collection components
+        let collection_component_1 =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+        let collection_component_2 =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Donate to both collections and sum the donations of the trophies
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(donation_account.wallet_address, XRD, dec!(150))
+            .take_from_worktop(XRD, dec!(100), "donation_amount_1")
+            .call_method_with_name_lookup(collection_component_1, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount_1"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .take_from_worktop(XRD, dec!(50), "donation_amount_2")
+            .call_method_with_name_lookup(collection_component_2, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount_2"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .take_all_from_worktop(base.trophy_resource_address, "trophies")
+            .create_proof_from_bucket_of_all("trophies", "trophies_proof")
+            .call_method_with_name_lookup(base.repository_component, "sum_donations", |lookup| {
+                (lookup.proof("trophies_proof"),)
+            })
+            .return_to_worktop("trophies")
+            .assert_worktop_contains(base.trophy_resource_address, dec!(2))
+            .deposit_batch(donation_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &donation_account.public_key,
+            )],
+            true,
+        );
+
+        let total: Decimal = receipt.expect_commit_success().output(7);
+        assert_eq!(total, dec!(150));
+    }
+
+    #[test]
+    fn earliest_created_success() {
+        let mut base = new_runner();
+
+        // Create an component admin account
+        let creator_badge_account = new_account(&mut base.test_runner);
+        let creator_badge_badge_id = mint_creator_badge(&mut base, &creator_badge_account);
+
+        // Create two collection components
+        let collection_component_1 =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+        let collection_component_2 =
+            new_collection_component(&mut base, &creator_badge_account, &creator_badge_badge_id);
+
+        // Donate to the first collection, backfill an older donation to the second collection and
+        // get the earliest created date of the trophies
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungible(
+                base.owner_account.wallet_address,
+                base.repository_owner_badge_global_id.clone(),
+            )
+            .withdraw_from_account(base.owner_account.wallet_address, XRD, dec!(150))
+            .take_from_worktop(XRD, dec!(100), "donation_amount_1")
+            .call_method_with_name_lookup(collection_component_1, "donate_mint", |lookup| {
+                (
+                    lookup.bucket("donation_amount_1"),
+                    None::<ComponentAddress>,
+                    None::<String>,
+                    false,
+                    None::<String>,
+                )
+            })
+            .take_from_worktop(XRD, dec!(50), "donation_amount_2")
+            .call_method_with_name_lookup(
+                collection_component_2,
+                "donate_mint_backfill",
+                |lookup| (lookup.bucket("donation_amount_2"), "2023-11-01T12:00"),
+            )
+            .take_all_from_worktop(base.trophy_resource_address, "trophies")
+            .create_proof_from_bucket_of_all("trophies", "trophies_proof")
+            .call_method_with_name_lookup(base.repository_component, "earliest_created", |lookup| {
+                (lookup.proof("trophies_proof"),)
+            })
+            .return_to_worktop("trophies")
+            .assert_worktop_contains(base.trophy_resource_address, dec!(2))
+            .deposit_batch(base.owner_account.wallet_address);
+
+        let receipt = execute_manifest(
+            &mut base.test_runner,
+            manifest,
+            "",
+            vec![NonFungibleGlobalId::from_public_key(
+                &base.owner_account.public_key,
+            )],
+            true,
+        );
+
+        let created: String = receipt.expect_commit_success().output(8);
+        assert_eq!(created, "2023-11-01T12:00");
+    }
 }
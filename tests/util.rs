@@ -0,0 +1,345 @@
+use backeum_blueprint::data::{CreatedGranularity, FeeMode, Transaction};
+use backeum_blueprint::util::{
+    add_donated, cap_transactions, compute_fee, compute_tier, created_granularity_of,
+    generate_created_string, generate_created_string_with_granularity, generate_trophy_url,
+    json_string, normalize_base_path, parse_created_string, round_to_divisibility,
+    validate_fee_mode, validate_info_url, validate_resource_description, validate_slug,
+    validate_url_template, DEFAULT_TROPHY_RESOURCE_DESCRIPTION, DEFAULT_TROPHY_URL_TEMPLATE,
+    MAX_FLAT_FEE, MAX_PERCENTAGE_FEE_BPS, MAX_TRANSACTIONS,
+};
+use scrypto::prelude::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn created_string_round_trip_success() {
+        for year in [1970, 2000, 2023, 2024, 2099] {
+            for (month, day, hour, minute) in [
+                (1, 1, 0, 0),
+                (2, 9, 7, 5),
+                (3, 5, 12, 30),
+                (9, 30, 18, 45),
+                (10, 1, 9, 1),
+                (12, 31, 23, 59),
+            ] {
+                let time = UtcDateTime::new(year, month, day, hour, minute, 0).unwrap();
+                let created = generate_created_string(time);
+
+                assert_eq!(
+                    created,
+                    format!(
+                        "{:04}-{:02}-{:02}T{:02}:{:02}",
+                        year, month, day, hour, minute
+                    )
+                );
+                assert_eq!(parse_created_string(created), time);
+            }
+        }
+    }
+
+    #[test]
+    fn created_string_leap_day_success() {
+        let time = UtcDateTime::new(2024, 2, 29, 0, 0, 0).unwrap();
+
+        assert_eq!(generate_created_string(time), "2024-02-29T00:00");
+        assert_eq!(parse_created_string("2024-02-29T00:00".to_owned()), time);
+    }
+
+    #[test]
+    fn parse_created_string_date_only_success() {
+        let time = UtcDateTime::new(2023, 11, 4, 0, 0, 0).unwrap();
+
+        assert_eq!(parse_created_string("2023-11-04".to_owned()), time);
+    }
+
+    #[test]
+    fn generate_created_string_with_granularity_success() {
+        let time = UtcDateTime::new(2023, 11, 4, 10, 19, 0).unwrap();
+
+        assert_eq!(
+            generate_created_string_with_granularity(time, &CreatedGranularity::Minute),
+            "2023-11-04T10:19"
+        );
+        assert_eq!(
+            generate_created_string_with_granularity(time, &CreatedGranularity::Day),
+            "2023-11-04"
+        );
+        assert_eq!(
+            created_granularity_of("2023-11-04T10:19"),
+            CreatedGranularity::Minute
+        );
+        assert_eq!(
+            created_granularity_of("2023-11-04"),
+            CreatedGranularity::Day
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn parse_created_string_failure_invalid_time() {
+        parse_created_string("2023-11-04T24:00".to_owned());
+    }
+
+    #[test]
+    #[should_panic]
+    fn parse_created_string_failure_unpadded() {
+        parse_created_string("2024-3-5".to_owned());
+    }
+
+    #[test]
+    #[should_panic]
+    fn parse_created_string_failure_invalid_date() {
+        parse_created_string("2023-02-29".to_owned());
+    }
+
+    #[test]
+    #[should_panic]
+    fn parse_created_string_failure_malformed() {
+        parse_created_string("not-a-date".to_owned());
+    }
+
+    #[test]
+    fn normalize_base_path_trailing_slash_success() {
+        let with_slash = normalize_base_path("https://backeum.com/".to_owned());
+        let without_slash = normalize_base_path("https://backeum.com".to_owned());
+
+        assert_eq!(with_slash, "https://backeum.com");
+        assert_eq!(with_slash, without_slash);
+        assert_eq!(
+            generate_trophy_url(
+                DEFAULT_TROPHY_URL_TEMPLATE,
+                with_slash,
+                dec!(150),
+                "2023-11-04T10:19".to_owned(),
+                "collection".to_owned(),
+                "currency".to_owned()
+            ),
+            generate_trophy_url(
+                DEFAULT_TROPHY_URL_TEMPLATE,
+                without_slash,
+                dec!(150),
+                "2023-11-04T10:19".to_owned(),
+                "collection".to_owned(),
+                "currency".to_owned()
+            )
+        );
+    }
+
+    #[test]
+    fn normalize_base_path_http_success() {
+        assert_eq!(
+            normalize_base_path("http://localhost:8080/".to_owned()),
+            "http://localhost:8080"
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn normalize_base_path_failure_scheme() {
+        normalize_base_path("backeum.com".to_owned());
+    }
+
+    #[test]
+    #[should_panic]
+    fn normalize_base_path_failure_host() {
+        normalize_base_path("https:///".to_owned());
+    }
+
+    #[test]
+    fn validate_info_url_success() {
+        assert_eq!(
+            validate_info_url("https://kansuler.com/support".to_owned()),
+            "https://kansuler.com/support"
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn validate_info_url_failure_scheme() {
+        validate_info_url("kansuler.com".to_owned());
+    }
+
+    #[test]
+    #[should_panic]
+    fn validate_info_url_failure_whitespace() {
+        validate_info_url("https://kansuler.com/my page".to_owned());
+    }
+
+    #[test]
+    fn cap_transactions_success() {
+        let mut transactions: Vec<Transaction> = (0..MAX_TRANSACTIONS + 5)
+            .map(|i| Transaction {
+                amount: Decimal::from(i as u64),
+                created: "2023-11-04T10:19".to_owned(),
+            })
+            .collect();
+
+        cap_transactions(&mut transactions);
+
+        // The oldest transactions are dropped
+        assert_eq!(transactions.len(), MAX_TRANSACTIONS);
+        assert_eq!(transactions.first().unwrap().amount, dec!(5));
+        assert_eq!(
+            transactions.last().unwrap().amount,
+            Decimal::from((MAX_TRANSACTIONS + 4) as u64)
+        );
+    }
+
+    #[test]
+    fn compute_tier_success() {
+        assert_eq!(compute_tier(dec!(0)), "bronze");
+        assert_eq!(compute_tier(dec!(99.99)), "bronze");
+        assert_eq!(compute_tier(dec!(100)), "silver");
+        assert_eq!(compute_tier(dec!(999.99)), "silver");
+        assert_eq!(compute_tier(dec!(1000)), "gold");
+    }
+
+    #[test]
+    fn compute_fee_success() {
+        assert_eq!(compute_fee(&FeeMode::Percentage(400), dec!(150)), dec!(6));
+        assert_eq!(compute_fee(&FeeMode::Percentage(0), dec!(150)), dec!(0));
+        assert_eq!(compute_fee(&FeeMode::Flat(dec!(5)), dec!(150)), dec!(5));
+        assert_eq!(compute_fee(&FeeMode::Flat(dec!(5)), dec!(3)), dec!(3));
+    }
+
+    #[test]
+    fn round_to_divisibility_success() {
+        assert_eq!(round_to_divisibility(dec!("0.0404"), 2), dec!("0.04"));
+        assert_eq!(round_to_divisibility(dec!("0.0499"), 2), dec!("0.04"));
+        assert_eq!(round_to_divisibility(dec!("1.5"), 0), dec!("1"));
+        assert_eq!(round_to_divisibility(dec!("0.0404"), 18), dec!("0.0404"));
+    }
+
+    #[test]
+    fn validate_slug_success() {
+        assert_eq!(validate_slug("kansuler-2-0".to_owned()), "kansuler-2-0");
+    }
+
+    #[test]
+    #[should_panic]
+    fn validate_slug_failure_uppercase() {
+        validate_slug("Kansuler".to_owned());
+    }
+
+    #[test]
+    #[should_panic]
+    fn validate_slug_failure_underscore() {
+        validate_slug("kansuler_2".to_owned());
+    }
+
+    #[test]
+    #[should_panic]
+    fn validate_slug_failure_empty() {
+        validate_slug("".to_owned());
+    }
+
+    #[test]
+    #[should_panic]
+    fn validate_slug_failure_not_url_safe() {
+        validate_slug("kansuler/page".to_owned());
+    }
+
+    #[test]
+    fn add_donated_success() {
+        assert_eq!(add_donated(Decimal::MAX - dec!(1), dec!(1)), Decimal::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "Donation total overflow")]
+    fn add_donated_failure_overflow() {
+        add_donated(Decimal::MAX - dec!(1), dec!(2));
+    }
+
+    #[test]
+    fn json_string_success() {
+        assert_eq!(json_string("Trophy name"), "\"Trophy name\"");
+        assert_eq!(
+            json_string("A \"quoted\" \\ name\n"),
+            "\"A \\\"quoted\\\" \\\\ name\\n\""
+        );
+    }
+
+    #[test]
+    fn validate_fee_mode_success_zero() {
+        validate_fee_mode(&FeeMode::Flat(dec!(0)));
+        validate_fee_mode(&FeeMode::Percentage(0));
+    }
+
+    #[test]
+    fn validate_fee_mode_success_max() {
+        validate_fee_mode(&FeeMode::Flat(Decimal::from(MAX_FLAT_FEE)));
+        validate_fee_mode(&FeeMode::Percentage(MAX_PERCENTAGE_FEE_BPS));
+    }
+
+    #[test]
+    #[should_panic(expected = "Flat fee must not be over 100.")]
+    fn validate_fee_mode_failure_flat_over_max() {
+        validate_fee_mode(&FeeMode::Flat(dec!(101)));
+    }
+
+    #[test]
+    #[should_panic(expected = "Flat fee must not be negative.")]
+    fn validate_fee_mode_failure_negative() {
+        validate_fee_mode(&FeeMode::Flat(dec!(-1)));
+    }
+
+    #[test]
+    #[should_panic(expected = "Percentage fee must not be over 1000 basis points.")]
+    fn validate_fee_mode_failure_percentage_over_max() {
+        validate_fee_mode(&FeeMode::Percentage(1001));
+    }
+
+    #[test]
+    fn generate_trophy_url_template_success() {
+        assert_eq!(
+            generate_trophy_url(
+                DEFAULT_TROPHY_URL_TEMPLATE,
+                "https://backeum.com".to_owned(),
+                dec!(150),
+                "2023-11-04T10:19".to_owned(),
+                "collection".to_owned(),
+                "currency".to_owned()
+            ),
+            "https://backeum.com/nft/collection/collection?donated=150&created=2023-11-04T10:19&currency=currency&tier=silver"
+        );
+        assert_eq!(
+            generate_trophy_url(
+                "{base}/render/{collection}/{donated}/{created}.png",
+                "https://backeum.com".to_owned(),
+                dec!(150),
+                "2023-11-04T10:19".to_owned(),
+                "collection".to_owned(),
+                "currency".to_owned()
+            ),
+            "https://backeum.com/render/collection/150/2023-11-04T10:19.png"
+        );
+    }
+
+    #[test]
+    fn validate_resource_description_success() {
+        assert_eq!(
+            validate_resource_description(DEFAULT_TROPHY_RESOURCE_DESCRIPTION.to_owned()),
+            DEFAULT_TROPHY_RESOURCE_DESCRIPTION
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn validate_resource_description_failure_empty() {
+        validate_resource_description("".to_owned());
+    }
+
+    #[test]
+    #[should_panic]
+    fn validate_resource_description_failure_too_long() {
+        validate_resource_description("a".repeat(256));
+    }
+
+    #[test]
+    #[should_panic]
+    fn validate_url_template_failure_missing_placeholder() {
+        validate_url_template("{base}/render/{collection}/{donated}.png".to_owned());
+    }
+}